@@ -1,10 +1,11 @@
-//! Demonstrate the tab completers: vec, hash-map, trie, and trie-map.
+//! Demonstrate the tab completers: vec, hash-map, trie, trie-map, fuzzy, and
+//! case-insensitive.
 //!
 //! Ask the user a question with tab completion.
 //!
 //! Unlike most bevy apps, this one accepts command line arguments. Without an
 //! argument, it will ask the user what kind of completer they want: vec,
-//! hash-map, trie, or trie-map.
+//! hash-map, trie, trie-map, fuzzy, or case-insensitive.
 //!
 //! This can be provided on the command line as the first argument like so:
 //!
@@ -31,6 +32,38 @@ fn hello_name_vec(mut minibuffer: Minibuffer) {
         );
 }
 
+fn hello_name_fuzzy(mut minibuffer: Minibuffer) {
+    minibuffer
+        .prompt_lookup(
+            "What's your name? ",
+            FuzzyLookup::new(vec!["John", "Sean", "Shane"]),
+        )
+        .observe(
+            |mut trigger: Trigger<Submit<String>>, mut minibuffer: Minibuffer| {
+                minibuffer.message(format!(
+                    "Hello, {}.",
+                    trigger.event_mut().take_result().unwrap()
+                ));
+            },
+        );
+}
+
+fn hello_name_case_insensitive(mut minibuffer: Minibuffer) {
+    minibuffer
+        .prompt_map(
+            "What's your name? ",
+            CaseInsensitive::new(vec!["John", "Sean", "Shane"]),
+        )
+        .observe(
+            |mut trigger: Trigger<Completed<String>>, mut minibuffer: Minibuffer| {
+                minibuffer.message(match trigger.event_mut().take_result().unwrap() {
+                    Ok(name) => format!("Hello, {name}."),
+                    Err(e) => format!("{e}"),
+                });
+            },
+        );
+}
+
 fn hello_name_trie(mut minibuffer: Minibuffer) {
     minibuffer
         .prompt_lookup(
@@ -99,6 +132,8 @@ fn plugin(app: &mut App) {
         hello_name_hash_map,
         hello_name_trie,
         hello_name_trie_map,
+        hello_name_fuzzy,
+        hello_name_case_insensitive,
     ));
 }
 
@@ -107,7 +142,7 @@ fn main() -> ExitCode {
     let argument: Option<String> = args.next();
     let is_help = argument.as_ref().map(|arg| arg == "-h" || arg == "--help");
     if is_help.unwrap_or(false) || args.next().is_some() {
-        eprintln!("usage: tab-completion <vec, hash-map, trie, trie-map>");
+        eprintln!("usage: tab-completion <vec, hash-map, trie, trie-map, fuzzy, case-insensitive>");
         return ExitCode::from(2);
     }
 
@@ -127,11 +162,16 @@ fn main() -> ExitCode {
     ExitCode::SUCCESS
 }
 
-const OPTIONS: [(&str, &str); 4] = [
+const OPTIONS: [(&str, &str); 6] = [
     ("vec (simple)", "hello_name_vec"),
     ("hash-map (maps to a value V)", "hello_name_hash_map"),
     ("trie (performant)", "hello_name_trie"),
     ("trie-map (performant)", "hello_name_trie_map"),
+    ("fuzzy (matches out of order)", "hello_name_fuzzy"),
+    (
+        "case-insensitive (ignores case)",
+        "hello_name_case_insensitive",
+    ),
 ];
 
 fn choose_completion(In(arg): In<Option<String>>, mut minibuffer: Minibuffer) {
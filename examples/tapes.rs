@@ -16,13 +16,15 @@ use bevy_minibuffer::prelude::*;
 mod common;
 
 fn plugin(app: &mut App) {
-    app.add_plugins(MinibufferPlugins).add_acts((
-        BasicActs::default(),
-        UniversalArgActs::default(),
-        TapeActs::default(),
-        // unscriptable::set_color,
-        Act::new_with_input(set_color),
-    ));
+    app.add_plugins(MinibufferPlugins)
+        .add_acts((
+            BasicActs::default(),
+            UniversalArgActs::default(),
+            TapeActs::default(),
+            // unscriptable::set_color,
+            Act::new_with_input(set_color),
+        ))
+        .register_act_input::<Option<Srgba>>();
 }
 
 fn main() {
@@ -221,10 +223,7 @@ mod unscriptable {
     pub(crate) use super::*;
 
     pub(crate) fn set_color(mut minibuffer: Minibuffer, selected: Res<Selected>) {
-        if selected.curr.is_none() {
-            minibuffer.message("Select a shape first.");
-        } else {
-            let selection = selected.curr.unwrap();
+        if let Some(selection) = selected.curr {
             minibuffer
                 .prompt_map("Hex color: ", bevy_minibuffer::autocomplete::SrgbaHexLookup)
                 .observe(
@@ -256,6 +255,8 @@ mod unscriptable {
                         }
                     },
                 );
+        } else {
+            minibuffer.message("Select a shape first.");
         }
     }
 }
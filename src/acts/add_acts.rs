@@ -1,4 +1,5 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, reflect::GetTypeRegistration};
+use std::{borrow::Cow, fmt::Debug};
 
 /// A marker for [ActBuilder]s.
 pub trait ActBuilders<Marker>: sealed::ActBuilders<Marker> {}
@@ -54,9 +55,18 @@ mod sealed {
     //     }
     // }
 
+    /// Put acts without a category in the category named after `P`.
+    fn categorize<P>(acts: &mut Acts) {
+        let category = super::category_name::<P>();
+        for builder in acts.values_mut() {
+            builder.category.get_or_insert_with(|| category.clone());
+        }
+    }
+
     impl<P: ActsPlugin> ActBuilders<ActsPluginMarker> for P {
         fn add_to_app(mut self, app: &mut App) {
-            let acts: Acts = self.take_acts();
+            let mut acts: Acts = self.take_acts();
+            categorize::<P>(&mut acts);
             <Acts as ActBuilders<ActsMarker>>::add_to_world(acts, app.world_mut());
             app.add_plugins(self);
         }
@@ -68,7 +78,8 @@ mod sealed {
 
     impl<P: ActsPluginGroup> ActBuilders<ActsPluginGroupMarker> for P {
         fn add_to_app(mut self, app: &mut App) {
-            let acts: Acts = self.take_acts();
+            let mut acts: Acts = self.take_acts();
+            categorize::<P>(&mut acts);
             <Acts as ActBuilders<ActsMarker>>::add_to_world(acts, app.world_mut());
             app.add_plugins(self);
         }
@@ -120,10 +131,33 @@ mod sealed {
     variadics_please::all_tuples!(impl_plugins_tuples, 0, 15, P, S);
 }
 
+/// Return the type name without its path or generic parameters, e.g.,
+/// "TapeActs".
+fn category_name<T>() -> Cow<'static, str> {
+    let name = std::any::type_name::<T>();
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name).to_owned().into()
+}
+
 /// An extension to App to add acts.
 pub trait AddActs {
     /// Adds the given acts to itself.
     fn add_acts<M>(&mut self, acts: impl ActBuilders<M>) -> &mut Self;
+
+    /// Register an act's input type for reflection.
+    ///
+    /// Inputs of registered types can be saved and loaded with tapes.
+    fn register_act_input<I>(&mut self) -> &mut Self
+    where
+        I: FromReflect + GetTypeRegistration + Clone + Default + Debug + Send + Sync;
+
+    /// Register an act's input type to be parsed from inline arguments.
+    ///
+    /// Acts with this input may be run like `spawn_enemy 5 "goblin"`. See
+    /// [ArgParserMap](crate::acts::ArgParserMap).
+    fn register_act_args<I>(&mut self) -> &mut Self
+    where
+        I: crate::acts::FromArgs + Send + Sync + 'static;
 }
 
 impl AddActs for App {
@@ -131,6 +165,24 @@ impl AddActs for App {
         acts.add_to_app(self);
         self
     }
+
+    fn register_act_input<I>(&mut self) -> &mut Self
+    where
+        I: FromReflect + GetTypeRegistration + Clone + Default + Debug + Send + Sync,
+    {
+        super::run_act::register_act_input::<I>(self);
+        self
+    }
+
+    fn register_act_args<I>(&mut self) -> &mut Self
+    where
+        I: crate::acts::FromArgs + Send + Sync + 'static,
+    {
+        self.world_mut()
+            .get_resource_or_init::<crate::acts::ArgParserMap>()
+            .register_from_args::<I>();
+        self
+    }
 }
 
 // impl<'w, 's> AddActs for Commands<'w, 's> {
@@ -0,0 +1,148 @@
+//! Other names acts answer to
+use crate::acts::Act;
+use bevy::prelude::*;
+use std::{borrow::Cow, collections::HashSet};
+
+/// Another name for an act
+///
+/// Aliases complete in 'run_act' and resolve [RunActByNameEvent]s like the
+/// act's name does, though another act's name takes precedence; see
+/// [Act::find_by_name]. A deprecated alias still works but hints at the act's
+/// name the first time it is used.
+///
+/// [RunActByNameEvent]: crate::event::RunActByNameEvent
+#[derive(Debug, Clone, PartialEq, Eq, Reflect)]
+pub struct ActAlias {
+    /// The alias
+    pub name: Cow<'static, str>,
+    /// Whether the alias is deprecated
+    pub deprecated: bool,
+}
+
+impl ActAlias {
+    /// Make an alias.
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            name: name.into(),
+            deprecated: false,
+        }
+    }
+
+    /// Make a deprecated alias.
+    pub fn deprecated(name: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            name: name.into(),
+            deprecated: true,
+        }
+    }
+}
+
+/// The deprecated aliases whose hint has been given
+#[derive(Resource, Debug, Default)]
+pub struct DeprecationHints(HashSet<Cow<'static, str>>);
+
+impl DeprecationHints {
+    /// Return a hint with the act's name the first time `name`, a deprecated
+    /// alias of `act`, is used.
+    pub fn hint(&mut self, act: &Act, name: &str) -> Option<String> {
+        let alias = act
+            .aliases
+            .iter()
+            .find(|alias| alias.deprecated && alias.name == name)?;
+        self.0
+            .insert(alias.name.clone())
+            .then(|| format!("'{}' is deprecated; use '{}'.", alias.name, act.name))
+    }
+}
+
+/// Find the names of one act that are also a name or alias of another act.
+///
+/// [Act::find_by_name] resolves such a name to the act it is the name of, or
+/// to the first act with it as an alias.
+pub fn find_alias_conflicts<'a>(
+    act_id: Entity,
+    acts: impl Iterator<Item = (Entity, &'a Act)>,
+) -> Vec<String> {
+    let acts: Vec<(Entity, &Act)> = acts.collect();
+    let Some((_, act)) = acts.iter().find(|(id, _)| *id == act_id) else {
+        return Vec::new();
+    };
+    let mut conflicts = Vec::new();
+    for (_, other) in acts.iter().filter(|(id, _)| *id != act_id) {
+        for alias in &act.aliases {
+            if alias.name == other.name {
+                conflicts.push(format!(
+                    "Alias '{}' of '{}' is the name of '{}'.",
+                    alias.name, act.name, other.name
+                ));
+            } else if other.aliases.iter().any(|a| a.name == alias.name) {
+                conflicts.push(format!(
+                    "Alias '{}' of '{}' is also an alias of '{}'.",
+                    alias.name, act.name, other.name
+                ));
+            }
+        }
+        for alias in other.aliases.iter().filter(|a| a.name == act.name) {
+            conflicts.push(format!(
+                "Alias '{}' of '{}' is the name of '{}'.",
+                alias.name, other.name, act.name
+            ));
+        }
+    }
+    conflicts
+}
+
+/// Warn about the names of one act that are also a name or alias of another.
+pub(crate) fn warn_alias_conflicts(act_id: Entity, world: &mut World) {
+    let mut query = world.query::<(Entity, &Act)>();
+    for conflict in find_alias_conflicts(act_id, query.iter(world)) {
+        warn!("{conflict}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::acts::{cache::NameActCache, ActBuilder, ActFlags};
+
+    fn list_acts() {}
+    fn show_acts() {}
+    fn ls() {}
+
+    #[test]
+    fn alias_conflicts() {
+        let mut world = World::new();
+        // The renamed act comes first so a plain search would find it.
+        let mut builder = ActBuilder::new(show_acts);
+        builder.deprecated_alias("list_acts").alias("ls");
+        Command::apply(builder, &mut world);
+        Command::apply(ActBuilder::new(list_acts), &mut world);
+        let mut builder = ActBuilder::new(ls);
+        builder.named("dir").alias("ls");
+        Command::apply(builder, &mut world);
+
+        let mut query = world.query::<(Entity, &Act)>();
+        let (list_acts_id, act) = Act::find_by_name(query.iter(&world), "list_acts").unwrap();
+        assert_eq!(act.name, "list_acts");
+        assert_eq!(
+            Act::find_by_name(query.iter(&world), "ls").unwrap().1.name,
+            "show_acts"
+        );
+        assert_eq!(
+            find_alias_conflicts(list_acts_id, query.iter(&world)),
+            ["Alias 'list_acts' of 'show_acts' is the name of 'list_acts'."]
+        );
+        let dir_id = Act::find_by_name(query.iter(&world), "dir").unwrap().0;
+        assert_eq!(
+            find_alias_conflicts(dir_id, query.iter(&world)),
+            ["Alias 'ls' of 'dir' is also an alias of 'show_acts'."]
+        );
+
+        let mut cache = NameActCache::default();
+        let trie = cache.trie(query.iter(&world), ActFlags::Active);
+        assert_eq!(
+            trie.exact_match("list_acts").map(|r| r.id),
+            Some(list_acts_id)
+        );
+    }
+}
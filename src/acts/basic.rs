@@ -2,9 +2,12 @@
 use crate::{
     acts::{
         cache::{HotkeyActCache, NameActCache},
-        ActFlags, ActRef, ActsPlugin,
+        keymaps::Keymaps,
+        split_args, ActFlags, ActRef, ActsPlugin, DeprecationHints,
     },
-    event::LastRunAct,
+    autocomplete::FuzzyLookup,
+    event::{LastRunAct, RunActByNameEvent},
+    history::HistoryCategory,
     input::{Hotkey, KeyChord},
     prelude::*,
     prelude::{keyseq, ActBuilder, Acts},
@@ -12,7 +15,7 @@ use crate::{
     sink, Minibuffer,
 };
 
-use std::{borrow::Cow, fmt::Debug};
+use std::{borrow::Cow, collections::HashMap, fmt::Debug};
 
 use crate::event::KeyChordEvent;
 use bevy::{prelude::*, window::RequestRedraw};
@@ -28,6 +31,7 @@ pub fn run_act(
     mut act_cache: ResMut<NameActCache>,
     mut acts: Query<(Entity, &Act)>,
     last_act: Res<LastRunAct>,
+    config: Res<Config>,
 ) {
     let prompt: Cow<'static, str> = last_act
         .hotkey(&mut acts.transmute_lens::<&Act>())
@@ -42,88 +46,292 @@ pub fn run_act(
             }
         })
         .unwrap_or("run_act: ".into());
-    let acts = act_cache.trie(acts.iter(), ActFlags::RunAct | ActFlags::Active);
-    minibuffer.prompt_map(prompt, acts.clone()).observe(
-        move |mut trigger: Trigger<Completed<ActRef>>, mut minibuffer: Minibuffer| match trigger
-            .event_mut()
-            .take()
-        {
-            Completed::Unhandled { result, input: _ } => match result {
-                Ok(act) => {
-                    minibuffer.run_act(act);
+    let flags = ActFlags::RunAct | ActFlags::Active;
+    let categories = act_cache.category_tries(acts.iter(), flags).to_vec();
+    let acts = act_cache.trie(acts.iter(), flags).clone();
+    if config.fuzzy_match {
+        let acts = categories.into_iter().fold(
+            Categorized::new(FuzzyLookup::new(acts)),
+            |lookup, (category, acts)| lookup.with_category(category, FuzzyLookup::new(acts)),
+        );
+        prompt_run_act_inline(&mut minibuffer, prompt, acts, config.inline_args);
+    } else {
+        let acts = categories
+            .into_iter()
+            .fold(Categorized::new(acts), |lookup, (category, acts)| {
+                lookup.with_category(category, acts)
+            });
+        prompt_run_act_inline(&mut minibuffer, prompt, acts, config.inline_args);
+    }
+}
+
+fn prompt_run_act_inline<L>(
+    minibuffer: &mut Minibuffer,
+    prompt: Cow<'static, str>,
+    lookup: L,
+    inline_args: bool,
+) where
+    L: Lookup + LookupMap<Item = ActRef> + Clone + Send + Sync + 'static,
+{
+    if inline_args {
+        prompt_run_act(minibuffer, prompt, CommandLine::new(lookup));
+    } else {
+        prompt_run_act(minibuffer, prompt, lookup);
+    }
+}
+
+fn prompt_run_act<L>(minibuffer: &mut Minibuffer, prompt: Cow<'static, str>, lookup: L)
+where
+    L: Lookup + LookupMap<Item = ActRef> + Clone + Send + Sync + 'static,
+{
+    minibuffer
+        .prompt_map(prompt, lookup)
+        .insert(HistoryCategory::new("run_act"))
+        .observe(run_act_completed);
+}
+
+fn run_act_completed(
+    mut trigger: Trigger<Completed<ActRef>>,
+    mut minibuffer: Minibuffer,
+    acts: Query<&Act>,
+    mut hints: ResMut<DeprecationHints>,
+) {
+    match trigger.event_mut().take() {
+        Completed::Unhandled { result, input } => match result {
+            Ok(act) => {
+                let name = input
+                    .as_deref()
+                    .and_then(|s| s.split_whitespace().next())
+                    .map(|s| s.rsplit_once('/').map_or(s, |(_, name)| name));
+                if let Some(hint) = name
+                    .zip(acts.get(act.id).ok())
+                    .and_then(|(name, a)| hints.hint(a, name))
+                {
+                    warn!("{hint}");
+                    minibuffer.message(hint);
                 }
-                Err(e) => {
-                    minibuffer.message(format!("{e}"));
+                // Anything after the act's name is an argument.
+                let args = match input.as_deref().map(split_args).transpose() {
+                    Ok(args) => args.unwrap_or_default(),
+                    Err(e) => {
+                        minibuffer.message(format!("{e}"));
+                        return;
+                    }
+                };
+                match acts.get(act.id) {
+                    Ok(a) if args.len() > 1 => {
+                        minibuffer.commands.trigger(
+                            RunActByNameEvent::new(a.name.clone())
+                                .with_args(args.into_iter().skip(1)),
+                        );
+                    }
+                    _ => minibuffer.run_act(act),
                 }
-            },
-            Completed::Handled => {
-                warn!("Unexpected handled.");
+            }
+            Err(e) => {
+                minibuffer.message(format!("{e}"));
             }
         },
-    );
+        Completed::Handled => {
+            warn!("Unexpected handled.");
+        }
+    }
+}
+
+/// An act with something that identifies it, e.g., its entity
+type Keyed<'a, K> = (K, &'a Act);
+
+/// Group acts by category and sort them by name. Acts without a category
+/// come last.
+fn by_category<'a, K>(
+    acts: impl Iterator<Item = Keyed<'a, K>>,
+) -> Vec<(Option<&'a str>, Vec<Keyed<'a, K>>)> {
+    let mut acts: Vec<Keyed<K>> = acts.collect();
+    acts.sort_by(|(_, a), (_, b)| {
+        (a.category.is_none(), &a.category, &a.name).cmp(&(
+            b.category.is_none(),
+            &b.category,
+            &b.name,
+        ))
+    });
+    let mut groups: Vec<(Option<&str>, Vec<Keyed<K>>)> = Vec::new();
+    for (key, act) in acts {
+        let category = act.category.as_deref();
+        match groups.last_mut() {
+            Some((last, group)) if *last == category => group.push((key, act)),
+            _ => groups.push((category, vec![(key, act)])),
+        }
+    }
+    groups
+}
+
+/// Return the heading for a category if there are any categories.
+fn category_heading(category: Option<&str>, headings: bool) -> Option<String> {
+    headings.then(|| format!("{}:", category.unwrap_or("Other")))
 }
 
 /// List acts currently operant.
 pub fn list_acts(acts: Query<&Act>) -> String {
-    let mut table = Table::new("{:<}  {:<}");
-    table.add_row(Row::new().with_cell("ACT ").with_cell("KEY BINDING"));
-    let mut acts: Vec<_> = acts.iter().collect();
-    acts.sort_by(|a, b| a.name.cmp(&b.name));
-    for act in &acts {
-        let mut name = Some(act.name.clone());
-        if act.hotkeys.is_empty() {
-            table.add_row(
-                Row::new()
-                    .with_cell(name.take().unwrap_or("".into()))
-                    .with_cell(""),
-            );
-        } else {
-            let bindings = act.hotkeys.iter().map(|hotkey| hotkey.to_string());
-
-            for binding in bindings {
+    let mut table = Table::new("{:<}  {:<}  {:<}");
+    table.add_row(
+        Row::new()
+            .with_cell("ACT ")
+            .with_cell("KEY BINDING")
+            .with_cell("DESCRIPTION"),
+    );
+    let groups = by_category(acts.iter().map(|act| ((), act)));
+    let headings = groups.iter().any(|(category, _)| category.is_some());
+    for (category, acts) in groups {
+        if let Some(heading) = category_heading(category, headings) {
+            table.add_heading(heading);
+        }
+        for (_, act) in acts {
+            let aliases: Vec<&str> = act
+                .aliases
+                .iter()
+                .filter(|alias| !alias.deprecated)
+                .map(|alias| alias.name.as_ref())
+                .collect();
+            let mut name: Option<Cow<'static, str>> = Some(if aliases.is_empty() {
+                act.name.clone()
+            } else {
+                format!("{} ({})", act.name, aliases.join(", ")).into()
+            });
+            let mut summary = Some(act.summary());
+            if act.hotkeys.is_empty() {
                 table.add_row(
                     Row::new()
                         .with_cell(name.take().unwrap_or("".into()))
-                        .with_cell(binding),
+                        .with_cell("")
+                        .with_cell(summary.take().unwrap_or("")),
                 );
+            } else {
+                let bindings = act.hotkeys.iter().map(|hotkey| hotkey.to_string());
+
+                for binding in bindings {
+                    table.add_row(
+                        Row::new()
+                            .with_cell(name.take().unwrap_or("".into()))
+                            .with_cell(binding)
+                            .with_cell(summary.take().unwrap_or("")),
+                    );
+                }
             }
         }
     }
     format!("{}", table)
 }
 
+/// Describe an act.
+///
+/// Asks for an act by name and shows its description, key bindings, flags,
+/// and input type.
+pub fn describe_act(
+    mut minibuffer: Minibuffer,
+    mut act_cache: ResMut<NameActCache>,
+    acts: Query<(Entity, &Act)>,
+) {
+    let acts = act_cache.trie(acts.iter(), ActFlags::empty());
+    minibuffer
+        .prompt_map("describe_act: ", acts.clone())
+        .observe(
+            |mut trigger: Trigger<Completed<ActRef>>,
+             mut minibuffer: Minibuffer,
+             acts: Query<&Act>| {
+                match trigger.event_mut().take_result() {
+                    Some(Ok(act_ref)) => {
+                        if let Ok(act) = acts.get(act_ref.id) {
+                            minibuffer.message(act_description(act));
+                        }
+                    }
+                    Some(Err(e)) => {
+                        minibuffer.message(format!("{e}"));
+                    }
+                    None => {
+                        warn!("Unexpected handled.");
+                    }
+                }
+            },
+        );
+}
+
+pub(crate) fn act_description(act: &Act) -> String {
+    let mut s = format!("{}\n", act.name);
+    if !act.description.is_empty() {
+        s.push_str(&act.description);
+        s.push('\n');
+    }
+    if !act.aliases.is_empty() {
+        let aliases: Vec<String> = act
+            .aliases
+            .iter()
+            .map(|alias| {
+                if alias.deprecated {
+                    format!("{} (deprecated)", alias.name)
+                } else {
+                    alias.name.to_string()
+                }
+            })
+            .collect();
+        s.push_str(&format!("Aliases: {}\n", aliases.join(", ")));
+    }
+    if let Some(ref category) = act.category {
+        s.push_str(&format!("Category: {category}\n"));
+    }
+    let bindings: Vec<String> = act.hotkeys.iter().map(|h| h.to_string()).collect();
+    s.push_str(&format!(
+        "Key bindings: {}\n",
+        if bindings.is_empty() {
+            "none".into()
+        } else {
+            bindings.join(", ")
+        }
+    ));
+    let flags: Vec<&str> = act.flags.iter_names().map(|(name, _)| name).collect();
+    s.push_str(&format!("Flags: {}", flags.join(" | ")));
+    if let Some(input) = act.input_type_name() {
+        s.push_str(&format!("\nInput: {input}"));
+    }
+    s
+}
+
 /// List key bindings available.
-pub fn list_key_bindings(acts: Query<&Act>) -> String {
+pub fn list_key_bindings(acts: Query<(Entity, &Act)>, keymaps: Res<Keymaps>) -> String {
     let mut table = Table::new("{:<}  {:<}");
     table.add_row(Row::new().with_cell("KEY BINDING ").with_cell("ACT"));
 
-    let mut key_bindings: Vec<(String, Cow<'static, str>)> = acts
-        .iter()
-        .flat_map(|act| {
-            act.hotkeys
-                .iter()
-                .map(|hotkey| (hotkey.to_string(), act.name.clone()))
-        })
-        .collect();
-    // Sort by key binding name? No.
-    // key_bindings.sort_by(|a, b| a.0.cmp(&b.0));
-    // Sort by act name? Yes.
-    key_bindings.sort_by(|a, b| a.1.cmp(&b.1));
-    for (binding, act) in key_bindings
+    // Only list the hotkeys that would run, i.e., not shadowed by a keymap.
+    let mut live: HashMap<Entity, Vec<usize>> = HashMap::new();
+    for (id, _, i) in keymaps.live_hotkeys(acts.iter()) {
+        live.entry(id).or_default().push(i);
+    }
+    // Sort by act name within each category; by_category does that.
+    let groups: Vec<_> = by_category(acts.iter())
         .into_iter()
-        // Don't show same act name in a row. Replace the same named items with
-        // an empty string. It's an implicit ibid.
-        .scan(Cow::from(""), |last, (bind, act)| {
-            if *last == act {
-                *last = act.clone();
-                Some((bind, Cow::from("")))
-            } else {
-                *last = act.clone();
-                Some((bind, act))
+        .filter(|(_, acts)| acts.iter().any(|(id, _)| live.contains_key(id)))
+        .collect();
+    let headings = groups.iter().any(|(category, _)| category.is_some());
+    for (category, acts) in groups {
+        if let Some(heading) = category_heading(category, headings) {
+            table.add_heading(heading);
+        }
+        for (id, act) in acts {
+            // Don't show same act name in a row. Replace the same named items
+            // with an empty string. It's an implicit ibid.
+            let mut name = Some(act.name.as_ref());
+            for &i in live.get(&id).into_iter().flatten() {
+                let hotkey = &act.hotkeys[i];
+                let key = match hotkey.keymap {
+                    Some(ref keymap) => format!("{hotkey} ({keymap})"),
+                    None => hotkey.to_string(),
+                };
+                table.add_row(
+                    Row::new()
+                        .with_cell(key)
+                        .with_cell(name.take().unwrap_or("")),
+                );
             }
-        })
-    {
-        table.add_row(Row::new().with_cell(binding).with_cell(act.into_owned()));
+        }
     }
     format!("{}", table)
 }
@@ -158,10 +366,11 @@ pub fn toggle_visibility(
 /// Allow the user to input a key chord sequence. Reveal the bindings it has.
 pub fn describe_key(
     acts: Query<(Entity, &Act)>,
+    keymaps: Res<Keymaps>,
     mut cache: ResMut<HotkeyActCache>,
     mut minibuffer: Minibuffer,
 ) {
-    let trie: Trie<_, _> = cache.trie(acts.iter()).clone();
+    let trie: Trie<_, _> = cache.trie(acts.iter(), &keymaps).clone();
     let mut position = trie.inc_search().into();
     // search
     let mut accum = Hotkey::empty();
@@ -233,28 +442,38 @@ impl Default for BasicActs {
             acts: Acts::new([
                 ActBuilder::new(list_acts.pipe(sink::string))
                     .named("list_acts")
+                    .description("List acts and their key bindings.")
                     .add_flags(ActFlags::ShowMinibuffer)
                     .sub_flags(ActFlags::Record)
                     .bind(keyseq! { Ctrl-H A }),
                 ActBuilder::new(list_key_bindings.pipe(sink::string))
                     .named("list_key_bindings")
+                    .description("List key bindings and their acts.")
                     .add_flags(ActFlags::ShowMinibuffer)
                     .sub_flags(ActFlags::Record)
                     .bind(keyseq! { Ctrl-H B }),
                 ActBuilder::new(toggle_visibility)
                     .named("toggle_visibility")
+                    .description("Hide or show the minibuffer.")
                     .bind(keyseq! { Backquote })
                     .sub_flags(ActFlags::RunAct),
                 ActBuilder::new(run_act)
                     .named("run_act")
+                    .description("Run an act by name.")
                     .bind_aliased(keyseq! { Shift-; }, ":")
                     .bind(keyseq! { Alt-X })
                     .add_flags(ActFlags::Adverb)
                     .sub_flags(ActFlags::RunAct | ActFlags::Record),
                 ActBuilder::new(describe_key)
                     .named("describe_key")
+                    .description("Reveal what act a key chord sequence runs.")
                     .bind(keyseq! { Ctrl-H K })
                     .sub_flags(ActFlags::Record),
+                ActBuilder::new(describe_act)
+                    .named("describe_act")
+                    .description("Describe an act's key bindings, flags, and input.")
+                    .bind(keyseq! { Ctrl-H D })
+                    .sub_flags(ActFlags::Record),
             ]),
         }
     }
@@ -287,3 +506,80 @@ impl ActsPlugin for BasicActs {
         &mut self.acts
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn noop() {}
+
+    fn speed(In(_speed): In<f32>) {}
+
+    #[test]
+    fn describe() {
+        let mut world = World::new();
+        world.init_resource::<crate::acts::RunActMap>();
+        let mut noop = ActBuilder::new(noop);
+        noop.named("noop")
+            .description("Do nothing.\nReally, nothing at all.")
+            .bind(keyseq! { Ctrl-N });
+        Command::apply(noop, &mut world);
+        Command::apply(ActBuilder::new_with_input(speed), &mut world);
+        let mut acts = world.query::<&Act>();
+        let noop = acts.iter(&world).find(|act| act.name == "noop").unwrap();
+        assert_eq!(noop.summary(), "Do nothing.");
+        assert_eq!(
+            act_description(noop),
+            "noop\nDo nothing.\nReally, nothing at all.\nKey bindings: Ctrl-N\nFlags: Active | RunAct | Record"
+        );
+        let speed = acts.iter(&world).find(|act| act.name == "speed").unwrap();
+        assert_eq!(speed.summary(), "");
+        assert!(act_description(speed).ends_with("Input: f32"));
+    }
+
+    #[test]
+    fn aliases() {
+        let mut world = World::new();
+        world.init_resource::<crate::acts::RunActMap>();
+        let mut noop = ActBuilder::new(noop);
+        noop.alias("nop").deprecated_alias("do_nothing");
+        Command::apply(noop, &mut world);
+        let mut acts = world.query::<&Act>();
+        let noop = acts.iter(&world).next().unwrap();
+        assert!(noop.is_named("nop") && noop.is_named("do_nothing"));
+        assert!(act_description(noop).contains("Aliases: nop, do_nothing (deprecated)\n"));
+        let list = world.run_system_cached(list_acts).unwrap();
+        assert!(list.contains("noop (nop)"), "{list}");
+        assert!(!list.contains("do_nothing"), "{list}");
+    }
+
+    #[test]
+    fn categories() {
+        let mut world = World::new();
+        world.init_resource::<crate::acts::RunActMap>();
+        world.init_resource::<Keymaps>();
+        let mut noop = ActBuilder::new(noop);
+        noop.category("Nothing").bind(keyseq! { Ctrl-N });
+        Command::apply(noop, &mut world);
+        let mut speed = ActBuilder::new_with_input(speed);
+        speed.bind(keyseq! { Ctrl-S });
+        Command::apply(speed, &mut world);
+        let list = world.run_system_cached(list_acts).unwrap();
+        let lines: Vec<&str> = list.lines().map(str::trim_end).collect();
+        assert_eq!(lines[1], "Nothing:", "{list}");
+        assert!(lines[2].starts_with("noop"), "{list}");
+        assert_eq!(lines[3], "Other:", "{list}");
+        assert!(lines[4].starts_with("speed"), "{list}");
+        let bindings = world.run_system_cached(list_key_bindings).unwrap();
+        let lines: Vec<&str> = bindings.lines().map(str::trim_end).collect();
+        assert_eq!(
+            lines[1..],
+            [
+                "Nothing:",
+                "Ctrl-N        noop",
+                "Other:",
+                "Ctrl-S        speed"
+            ]
+        );
+    }
+}
@@ -3,6 +3,7 @@ use crate::{
     acts::{
         basic::BasicActs,
         cache::{HotkeyActCache, NameActCache},
+        keymaps::Keymaps,
         ActFlags, ActRef, ActsPlugin,
     },
     event::LastRunAct,
@@ -28,9 +29,12 @@ pub fn run_act(
     mut acts: Query<(Entity, &Act)>,
     last_act: Res<LastRunAct>,
 ) -> impl Future<Output = Result<(), crate::Error>> {
-    let acts_trie = act_cache
-        .trie(acts.iter(), ActFlags::RunAct | ActFlags::Active)
-        .clone();
+    let flags = ActFlags::RunAct | ActFlags::Active;
+    let categories = act_cache.category_tries(acts.iter(), flags).to_vec();
+    let acts_trie = categories.into_iter().fold(
+        Categorized::new(act_cache.trie(acts.iter(), flags).clone()),
+        |lookup, (category, acts)| lookup.with_category(category, acts),
+    );
     let prompt: Cow<'static, str> = last_act
         .hotkey(&mut acts.transmute_lens::<&Act>())
         .map(|hotkey| {
@@ -61,6 +65,7 @@ pub fn run_act(
 /// Input a key sequence. This will tell you what it does.
 pub fn describe_key(
     acts: Query<(Entity, &Act)>,
+    keymaps: Res<Keymaps>,
     mut cache: ResMut<HotkeyActCache>,
     mut minibuffer: MinibufferAsync,
 ) -> impl Future<Output = Result<(), crate::Error>> {
@@ -69,7 +74,7 @@ pub fn describe_key(
         .iter()
         .map(|(id, act)| (id, act.name.clone()))
         .collect();
-    let trie: Trie<_, _> = cache.trie(acts.iter()).clone();
+    let trie: Trie<_, _> = cache.trie(acts.iter(), &keymaps).clone();
     async move {
         let mut search = trie.inc_search();
         let prompt = "Press key: ";
@@ -84,16 +89,14 @@ pub fn describe_key(
                         .value()
                         .and_then(|act_ref: &ActRef| act_names.get(&act_ref.id));
                     let msg = match x {
-                        Answer::Match => format!(
-                            "{}is bound to {}",
-                            accum,
-                            name.as_deref().unwrap_or(&"???".into())
-                        ),
+                        Answer::Match => {
+                            format!("{}is bound to {}", accum, name.unwrap_or(&"???".into()))
+                        }
                         Answer::PrefixAndMatch => {
                             format!(
                                 "{}is bound to {} and more",
                                 accum,
-                                name.as_deref().unwrap_or(&"???".into())
+                                name.unwrap_or(&"???".into())
                             )
                         }
                         Answer::Prefix => accum.clone(),
@@ -148,12 +151,14 @@ impl Default for BasicAsyncActs {
             acts: Acts::new([
                 ActBuilder::new(run_act.pipe(sink::future_result))
                     .named("run_act")
+                    .description("Run an act by name.")
                     .bind_aliased(keyseq! { Shift-; }, ":")
                     .bind(keyseq! { Alt-X })
                     .add_flags(ActFlags::Adverb)
                     .sub_flags(ActFlags::RunAct),
                 ActBuilder::new(describe_key.pipe(sink::future_result))
                     .named("describe_key")
+                    .description("Reveal what act a key chord sequence runs.")
                     .bind(keyseq! { Ctrl-H K }),
             ]),
         }
@@ -0,0 +1,217 @@
+//! Save and load key bindings
+//!
+//! A keymap file maps act names to key sequences written as text. A key
+//! sequence in a keymap other than the global one is prefixed by its name. An
+//! act in the file has its default bindings replaced; an empty list unbinds
+//! it.
+//!
+//! ```ron
+//! {
+//!     "run_act": ["Alt-X", "Shift-;"],
+//!     "tape_record": ["Ctrl-R"],
+//!     "save_level": ["editor: Ctrl-S"],
+//!     "list_key_bindings": [],
+//! }
+//! ```
+use crate::{
+    acts::{find_conflicts_with, Act},
+    input::Hotkey,
+    Error,
+};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::Path};
+
+/// Act names and their key sequences
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize, Deref, DerefMut)]
+#[serde(transparent)]
+pub struct KeyBindings(pub BTreeMap<String, Vec<String>>);
+
+impl KeyBindings {
+    /// Collect the current bindings of acts.
+    pub fn from_acts<'a>(acts: impl Iterator<Item = &'a Act>) -> Self {
+        KeyBindings(
+            acts.map(|act| {
+                (
+                    act.name.to_string(),
+                    act.hotkeys.iter().map(Hotkey::qualified).collect(),
+                )
+            })
+            .collect(),
+        )
+    }
+
+    /// Write key bindings as RON.
+    pub fn to_ron(&self) -> Result<String, Error> {
+        Ok(ron::ser::to_string_pretty(
+            self,
+            ron::ser::PrettyConfig::default(),
+        )?)
+    }
+
+    /// Read key bindings from RON.
+    pub fn from_ron(input: &str) -> Result<Self, Error> {
+        Ok(ron::from_str(input)?)
+    }
+
+    /// Replace the bindings of the acts named here.
+    ///
+    /// Acts that are not found and key sequences that do not parse are
+    /// returned as errors; everything else is still applied. So are conflicts
+    /// with the new bindings. A binding that was already present keeps its
+    /// alias.
+    pub fn apply(&self, world: &mut World) -> Vec<Error> {
+        let mut errors = Vec::new();
+        let mut query = world.query::<(Entity, &Act)>();
+        let mut updates = Vec::new();
+        for (name, keys) in self.iter() {
+            let Some((id, act)) = Act::find_by_name(query.iter(world), name) else {
+                errors.push(Error::Message(
+                    format!("No act named '{name}' in keymap file.").into(),
+                ));
+                continue;
+            };
+            let mut hotkeys = Vec::new();
+            for key in keys {
+                match key.parse::<Hotkey>() {
+                    Ok(hotkey) => hotkeys.push(
+                        act.find_hotkey_in(hotkey.keymap.as_deref(), &hotkey.chords)
+                            .cloned()
+                            .unwrap_or(hotkey),
+                    ),
+                    Err(e) => errors.push(Error::Message(
+                        format!("Bad key {key:?} for act '{name}' in keymap file: {e}").into(),
+                    )),
+                }
+            }
+            updates.push((id, hotkeys));
+        }
+        let ids: Vec<Entity> = updates.iter().map(|(id, _)| *id).collect();
+        for (id, hotkeys) in updates {
+            if let Err(e) = Act::set_hotkeys(id, hotkeys, world) {
+                errors.push(e);
+            }
+        }
+        let mut conflicts = Vec::new();
+        for id in ids {
+            for conflict in find_conflicts_with(id, query.iter(world)) {
+                if !conflicts.contains(&conflict) {
+                    conflicts.push(conflict);
+                }
+            }
+        }
+        errors.extend(conflicts.into_iter().map(Error::from));
+        errors
+    }
+}
+
+/// Save the bindings of all acts to a file.
+///
+/// Returns the number of acts saved.
+pub fn save_keymap(path: &Path, world: &mut World) -> Result<usize, Error> {
+    let mut query = world.query::<&Act>();
+    let bindings = KeyBindings::from_acts(query.iter(world));
+    std::fs::write(path, bindings.to_ron()?)?;
+    Ok(bindings.len())
+}
+
+/// Load bindings from a file and apply them.
+///
+/// Returns the problems found in an otherwise readable file.
+pub fn load_keymap(path: &Path, world: &mut World) -> Result<Vec<Error>, Error> {
+    let contents = std::fs::read_to_string(path)?;
+    Ok(KeyBindings::from_ron(&contents)?.apply(world))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{acts::ActBuilder, prelude::keyseq};
+
+    fn run_act() {}
+    fn tape_record() {}
+
+    #[test]
+    fn apply_key_bindings() {
+        let mut world = World::new();
+        let mut builder = ActBuilder::new(run_act);
+        builder.bind_aliased(keyseq! { Shift-; }, ":");
+        builder.bind(keyseq! { Alt-X });
+        Command::apply(builder, &mut world);
+        let mut builder = ActBuilder::new(tape_record);
+        builder.bind(keyseq! { Q });
+        Command::apply(builder, &mut world);
+
+        let bindings = KeyBindings::from_ron(
+            r#"{
+                "run_act": ["Shift-;", "Ctrl-Nope"],
+                "tape_record": ["Ctrl-R", "Ctrl-H R"],
+                "missing": ["A"],
+            }"#,
+        )
+        .unwrap();
+        let errors = bindings.apply(&mut world);
+        assert_eq!(errors.len(), 2, "{errors:?}");
+
+        let mut query = world.query::<&Act>();
+        let saved = KeyBindings::from_acts(query.iter(&world));
+        assert_eq!(saved["run_act"], vec!["Shift-;"]);
+        assert_eq!(saved["tape_record"], vec!["Ctrl-R", "Ctrl-H R"]);
+        let run_act = query
+            .iter(&world)
+            .find(|act| act.name == "run_act")
+            .unwrap();
+        assert_eq!(run_act.hotkeys[0].alias.as_deref(), Some(":"));
+
+        let ron = saved.to_ron().unwrap();
+        assert_eq!(KeyBindings::from_ron(&ron).unwrap(), saved);
+    }
+
+    fn save_game() {}
+
+    #[test]
+    fn keymap_bindings() {
+        let mut world = World::new();
+        let mut builder = ActBuilder::new(save_game);
+        builder
+            .bind(keyseq! { Ctrl-S })
+            .bind_in("editor", keyseq! { Ctrl-S })
+            .bind_in("menu", keyseq! { Ctrl-S });
+        Command::apply(builder, &mut world);
+        let act_id = world
+            .query_filtered::<Entity, With<Act>>()
+            .single(&world)
+            .unwrap();
+
+        let mut query = world.query::<&Act>();
+        let saved = KeyBindings::from_acts(query.iter(&world));
+        assert_eq!(
+            saved["save_game"],
+            vec!["Ctrl-S", "editor: Ctrl-S", "menu: Ctrl-S"]
+        );
+
+        // Each keymap is unbound on its own.
+        let menu: Hotkey = "menu: Ctrl-S".parse().unwrap();
+        let unbound = Act::unbind_hotkey(act_id, &menu, &mut world).unwrap();
+        assert_eq!(unbound.keymap.as_deref(), Some("menu"));
+        assert!(Act::unbind_hotkey(act_id, &menu, &mut world).is_err());
+        let editor: Hotkey = "editor: Ctrl-S".parse().unwrap();
+        assert!(Act::bind_hotkey(act_id, editor, &mut world).is_err());
+        let help: Hotkey = "editor: Ctrl-H".parse().unwrap();
+        Act::bind_hotkey(act_id, help, &mut world).unwrap();
+
+        let path = std::env::temp_dir().join(format!("keymap_{}.ron", std::process::id()));
+        assert_eq!(save_keymap(&path, &mut world).unwrap(), 1);
+        Act::set_hotkeys(act_id, Vec::new(), &mut world).unwrap();
+        assert!(load_keymap(&path, &mut world).unwrap().is_empty());
+        std::fs::remove_file(&path).unwrap();
+        let hotkeys: Vec<String> = world
+            .get::<Act>(act_id)
+            .unwrap()
+            .hotkeys
+            .iter()
+            .map(Hotkey::qualified)
+            .collect();
+        assert_eq!(hotkeys, ["Ctrl-S", "editor: Ctrl-S", "editor: Ctrl-H"]);
+    }
+}
@@ -0,0 +1,322 @@
+//! Change key bindings at runtime
+use crate::{
+    acts::{
+        cache::NameActCache, find_conflicts, Act, ActBuilder, ActFlags, ActRef, Acts, ActsPlugin,
+    },
+    autocomplete::Completed,
+    event::DispatchEvent,
+    input::Hotkey,
+    prompt::{Submit, TextField},
+    sink, Minibuffer,
+};
+use bevy::prelude::*;
+use std::path::PathBuf;
+
+pub mod key_bindings;
+pub use key_bindings::KeyBindings;
+
+/// Acts to change key bindings at runtime
+///
+/// Adds 'bind_key', 'unbind_key', 'save_keymap', and 'list_conflicts' acts. Keys are entered as
+/// text like "Ctrl-H A" or, in a keymap, "editor: Ctrl-S".
+#[derive(Debug, Deref, DerefMut)]
+pub struct BindingActs {
+    /// Set of acts
+    #[deref]
+    pub acts: Acts,
+    keymap: Option<PathBuf>,
+}
+
+impl BindingActs {
+    /// Load key bindings from a keymap file at startup.
+    ///
+    /// Its bindings replace the defaults of any act added before startup.
+    /// 'save_keymap' writes to this file. It is fine if the file does not
+    /// exist yet. See [KeyBindings] for the format.
+    pub fn keymap(mut self, path: impl Into<PathBuf>) -> Self {
+        self.keymap = Some(path.into());
+        self
+    }
+}
+
+/// The keymap file given to [BindingActs::keymap]
+#[derive(Resource, Debug, Clone, Deref)]
+pub struct KeymapFile(pub PathBuf);
+
+impl Default for BindingActs {
+    fn default() -> Self {
+        Self {
+            keymap: None,
+            acts: Acts::new([
+                Act::new(bind_key)
+                    .description("Bind a key sequence to an act.")
+                    .sub_flags(ActFlags::Record),
+                Act::new(unbind_key)
+                    .description("Unbind a key sequence.")
+                    .sub_flags(ActFlags::Record),
+                Act::new(save_keymap)
+                    .description("Save key bindings to the keymap file.")
+                    .sub_flags(ActFlags::Record),
+                ActBuilder::new(list_conflicts.pipe(sink::string))
+                    .named("list_conflicts")
+                    .description("List key bindings that shadow one another.")
+                    .add_flags(ActFlags::ShowMinibuffer)
+                    .sub_flags(ActFlags::Record),
+            ]),
+        }
+    }
+}
+
+impl Plugin for BindingActs {
+    fn build(&self, app: &mut App) {
+        if let Some(path) = &self.keymap {
+            app.insert_resource(KeymapFile(path.clone()))
+                .add_systems(PostStartup, load_keymap_file);
+        }
+        self.warn_on_unused_acts();
+    }
+}
+
+impl ActsPlugin for BindingActs {
+    fn acts(&self) -> &Acts {
+        &self.acts
+    }
+    fn acts_mut(&mut self) -> &mut Acts {
+        &mut self.acts
+    }
+}
+
+/// Ask for a key sequence and an act, then bind them.
+pub fn bind_key(mut minibuffer: Minibuffer) {
+    minibuffer.prompt::<TextField>("Bind key: ").observe(
+        |mut trigger: Trigger<Submit<String>>,
+         mut minibuffer: Minibuffer,
+         mut act_cache: ResMut<NameActCache>,
+         acts: Query<(Entity, &Act)>| {
+            let Ok(input) = trigger.event_mut().take_result() else {
+                return;
+            };
+            let hotkey: Hotkey = match input.parse() {
+                Ok(hotkey) => hotkey,
+                Err(e) => {
+                    minibuffer.message(format!("{e}"));
+                    return;
+                }
+            };
+            let acts = act_cache.trie(acts.iter(), ActFlags::empty()).clone();
+            minibuffer
+                .prompt_map(format!("Bind {} to act: ", hotkey.qualified()), acts)
+                .observe(
+                    move |mut trigger: Trigger<Completed<ActRef>>,
+                          mut minibuffer: Minibuffer,
+                          mut commands: Commands| {
+                        match trigger.event_mut().take_result() {
+                            Some(Ok(act_ref)) => {
+                                let hotkey = hotkey.clone();
+                                commands.queue(move |world: &mut World| {
+                                    bind_and_report(act_ref.id, hotkey, world)
+                                });
+                            }
+                            Some(Err(e)) => {
+                                minibuffer.message(format!("{e}"));
+                            }
+                            None => {
+                                warn!("Unexpected handled.");
+                            }
+                        }
+                    },
+                );
+        },
+    );
+}
+
+fn bind_and_report(act_id: Entity, hotkey: Hotkey, world: &mut World) {
+    let keys = hotkey.qualified();
+    let msg = match Act::bind_hotkey(act_id, hotkey, world) {
+        Ok(()) => {
+            let name = world
+                .get::<Act>(act_id)
+                .map(|act| act.name.clone())
+                .unwrap_or_default();
+            format!("Bound {keys} to {name}.")
+        }
+        Err(e) => format!("{e}"),
+    };
+    world.trigger(DispatchEvent::EmitMessage(msg));
+}
+
+/// Ask for a key sequence and unbind it from any act.
+pub fn unbind_key(mut minibuffer: Minibuffer) {
+    minibuffer.prompt::<TextField>("Unbind key: ").observe(
+        |mut trigger: Trigger<Submit<String>>,
+         mut minibuffer: Minibuffer,
+         mut commands: Commands,
+         acts: Query<(Entity, &Act)>| {
+            let Ok(input) = trigger.event_mut().take_result() else {
+                return;
+            };
+            let hotkey: Hotkey = match input.parse() {
+                Ok(hotkey) => hotkey,
+                Err(e) => {
+                    minibuffer.message(format!("{e}"));
+                    return;
+                }
+            };
+            let bound: Vec<Entity> = acts
+                .iter()
+                .filter(|(_, act)| {
+                    act.find_hotkey_in(hotkey.keymap.as_deref(), &hotkey.chords)
+                        .is_some()
+                })
+                .map(|(id, _)| id)
+                .collect();
+            if bound.is_empty() {
+                minibuffer.message(format!("{} is unbound", hotkey.qualified()));
+                return;
+            }
+            commands.queue(move |world: &mut World| {
+                let mut names = Vec::new();
+                for id in bound {
+                    match Act::unbind_hotkey(id, &hotkey, world) {
+                        Ok(_) => {
+                            if let Some(act) = world.get::<Act>(id) {
+                                names.push(act.name.to_string());
+                            }
+                        }
+                        Err(e) => warn!("{e}"),
+                    }
+                }
+                let msg = format!("Unbound {} from {}.", hotkey.qualified(), names.join(", "));
+                world.trigger(DispatchEvent::EmitMessage(msg));
+            });
+        },
+    );
+}
+
+fn load_keymap_file(world: &mut World) {
+    let Some(path) = world.get_resource::<KeymapFile>().map(|x| x.0.clone()) else {
+        return;
+    };
+    if !path.exists() {
+        info!("No keymap file {path:?}.");
+        return;
+    }
+    let errors = match key_bindings::load_keymap(&path, world) {
+        Ok(errors) => errors,
+        Err(e) => vec![e],
+    };
+    if errors.is_empty() {
+        info!("Loaded keymap from {path:?}.");
+        return;
+    }
+    let msg = errors
+        .iter()
+        .map(|e| e.to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+    warn!("Problems loading keymap {path:?}: {msg}");
+    world.send_event(DispatchEvent::EmitMessage(msg));
+}
+
+/// Save the key bindings of all acts.
+///
+/// Writes to the [KeymapFile] if there is one; otherwise asks for a path.
+pub fn save_keymap(
+    keymap_file: Option<Res<KeymapFile>>,
+    mut minibuffer: Minibuffer,
+    mut commands: Commands,
+) {
+    if let Some(path) = keymap_file {
+        let path = path.0.clone();
+        commands.queue(move |world: &mut World| save_and_report(path, world));
+    } else {
+        minibuffer.prompt::<TextField>("Save keymap to: ").observe(
+            |mut trigger: Trigger<Submit<String>>, mut commands: Commands| {
+                if let Ok(path) = trigger.event_mut().take_result() {
+                    commands.queue(move |world: &mut World| {
+                        save_and_report(PathBuf::from(path), world)
+                    });
+                }
+            },
+        );
+    }
+}
+
+/// List every shadowed key binding.
+pub fn list_conflicts(acts: Query<(Entity, &Act)>) -> String {
+    let conflicts = find_conflicts(acts.iter());
+    if conflicts.is_empty() {
+        "No conflicts.".into()
+    } else {
+        conflicts
+            .iter()
+            .map(|conflict| conflict.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+fn save_and_report(path: PathBuf, world: &mut World) {
+    let msg = match key_bindings::save_keymap(&path, world) {
+        Ok(count) => format!("Saved bindings of {count} acts to {}.", path.display()),
+        Err(e) => format!("Could not save keymap: {e}"),
+    };
+    world.trigger(DispatchEvent::EmitMessage(msg));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        acts::{cache::HotkeyActCache, keymaps::Keymaps},
+        input::KeyChord,
+        prelude::keyseq,
+    };
+    use bevy_input_sequence::input_sequence::KeySequence;
+
+    fn noop() {}
+
+    fn keyseqs(world: &mut World, act_id: Entity) -> Vec<Vec<KeyChord>> {
+        let children: Vec<Entity> = world
+            .get::<Children>(act_id)
+            .map(|children| children.iter().collect())
+            .unwrap_or_default();
+        children
+            .into_iter()
+            .filter_map(|id| world.get::<KeySequence>(id))
+            .map(|keyseq| keyseq.acts.clone())
+            .collect()
+    }
+
+    #[test]
+    fn rebind() {
+        let mut world = World::new();
+        world.init_resource::<HotkeyActCache>();
+        let mut builder = Act::new(noop);
+        builder.bind(keyseq! { Ctrl-A });
+        Command::apply(builder, &mut world);
+        let act_id = world
+            .query_filtered::<Entity, With<Act>>()
+            .single(&world)
+            .unwrap();
+        assert_eq!(keyseqs(&mut world, act_id).len(), 1);
+
+        let hotkey: Hotkey = "Ctrl-H X".parse().unwrap();
+        Act::bind_hotkey(act_id, hotkey.clone(), &mut world).unwrap();
+        assert!(Act::bind_hotkey(act_id, hotkey.clone(), &mut world).is_err());
+        assert_eq!(world.get::<Act>(act_id).unwrap().hotkeys.len(), 2);
+        assert_eq!(keyseqs(&mut world, act_id).len(), 2);
+
+        let ctrl_a: Hotkey = "Ctrl-A".parse().unwrap();
+        Act::unbind_hotkey(act_id, &ctrl_a, &mut world).unwrap();
+        assert!(Act::unbind_hotkey(act_id, &ctrl_a, &mut world).is_err());
+        assert_eq!(keyseqs(&mut world, act_id), vec![hotkey.chords.clone()]);
+
+        world.resource_scope(|world, mut cache: Mut<HotkeyActCache>| {
+            let mut acts = world.query::<(Entity, &Act)>();
+            let trie = cache.trie(acts.iter(world), &Keymaps::default());
+            assert!(trie.exact_match(&hotkey.chords).is_some());
+            assert!(trie.exact_match(&ctrl_a.chords).is_none());
+        });
+    }
+}
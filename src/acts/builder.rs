@@ -1,8 +1,13 @@
 //! Acts and their flags, builders, and collections
 use crate::{
-    acts::{Act, ActFlags, ActWithInputSystem, RunActMap},
+    acts::{
+        cache::{HotkeyActCache, NameActCache},
+        warn_alias_conflicts, warn_conflicts, Act, ActAlias, ActFlags, ActWithInputSystem,
+        RunActMap,
+    },
     input::Hotkey,
     ui::ActContainer,
+    Config,
 };
 use bevy::{ecs::system::EntityCommand, prelude::*};
 use bevy_input_sequence::KeyChord;
@@ -16,33 +21,62 @@ use std::{
     },
 };
 
+/// What to do when an act is added with the name of an existing act
+///
+/// Set it with [Config::duplicate_acts].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect)]
+pub enum DuplicateActPolicy {
+    /// Log an error and do not add the new act.
+    Reject,
+    /// Log a warning and replace the existing act.
+    #[default]
+    Replace,
+    /// Add the new act with a numeric suffix, e.g., "act_2".
+    Suffix,
+}
+
+/// Registers a system and returns its entity
+type RegisterSystem = Box<dyn FnOnce(&mut World) -> Entity + 'static + Send + Sync>;
+
 /// Builds an [Act]
 // #[derive(Debug)]
 pub struct ActBuilder {
     pub name: Cow<'static, str>,
+    /// Other names
+    pub aliases: Vec<ActAlias>,
+    /// Category to group the act under
+    pub category: Option<Cow<'static, str>>,
     /// Hotkeys
     pub hotkeys: Vec<Hotkey>,
     system_name: Cow<'static, str>,
-    register_system: Box<dyn FnOnce(&mut World) -> Entity + 'static + Send + Sync>,
+    register_system: RegisterSystem,
+    register_condition: Option<RegisterSystem>,
     // pub(crate) system: Option<BoxedSystem>,
     /// Flags for this act
     pub flags: ActFlags,
     /// Shorten the name to just the first system.
     pub shorten_name: bool,
+    /// Description of what the act does
+    pub description: Cow<'static, str>,
     input: Option<TypeId>,
+    input_name: Option<&'static str>,
 }
 
 impl fmt::Debug for ActBuilder {
     fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
         fmt.debug_struct("ActBuilder")
             .field("name", &self.name)
+            .field("aliases", &self.aliases)
+            .field("category", &self.category)
             .field("hotkeys", &self.hotkeys)
             .field(
                 "register_system",
                 &"Box<dyn FnOnce(&mut World) -> Entity { ... }",
             )
+            .field("run_if", &self.register_condition.is_some())
             .field("flags", &self.flags)
             .field("shorten_name", &self.shorten_name)
+            .field("description", &self.description)
             .finish()
     }
 }
@@ -58,15 +92,20 @@ impl ActBuilder {
         let name = Self::name_for_system(&system, true);
         ActBuilder {
             name,
+            aliases: Vec::new(),
+            category: None,
             system_name,
             register_system: Box::new(move |world: &mut World| {
                 let system_id = world.register_system(system);
                 system_id.entity()
             }),
+            register_condition: None,
             hotkeys: Vec::new(),
             flags: ActFlags::default(),
             shorten_name: true,
+            description: "".into(),
             input: None,
+            input_name: None,
         }
     }
 
@@ -80,6 +119,8 @@ impl ActBuilder {
         let name = Self::name_for_system(&system, true);
         ActBuilder {
             name,
+            aliases: Vec::new(),
+            category: None,
             system_name,
             register_system: Box::new(move |world: &mut World| {
                 let mut run_act_map = world.resource_mut::<RunActMap>();
@@ -90,10 +131,13 @@ impl ActBuilder {
                 let system_id = world.register_system(system);
                 system_id.entity()
             }),
+            register_condition: None,
             hotkeys: Vec::new(),
             flags: ActFlags::default(),
             shorten_name: true,
+            description: "".into(),
             input: Some(TypeId::of::<I>()),
+            input_name: Some(std::any::type_name::<I>()),
         }
     }
 
@@ -129,28 +173,116 @@ impl ActBuilder {
         let name = self.name;
         // let id = (self.make_act_runner)(world);
         let system_id = (self.register_system)(world);
+        let condition = self.register_condition.map(|register| register(world));
         // let id = system_id.entity();
         // world.get_entity_mut(id).expect("entity for system_id")
         //     .insert(RunActMap::new(ActSystem(system_id)));
         (
             Act {
                 name,
+                aliases: self.aliases,
+                category: self.category,
                 hotkeys: self.hotkeys,
                 flags: self.flags,
                 system_id,
+                condition,
                 system_name: self.system_name,
+                description: self.description,
                 input: self.input,
+                input_name: self.input_name,
             },
             system_id,
         )
     }
 
+    /// Apply the [DuplicateActPolicy] from [Config] before adding the act as
+    /// entity `id` if it is given. Return false if the act should not be
+    /// added.
+    fn resolve_duplicate(&mut self, id: Option<Entity>, world: &mut World) -> bool {
+        let mut query = world.query::<(Entity, &Act)>();
+        let Some(existing) = query
+            .iter(world)
+            .find(|(act_id, act)| act.name == self.name && Some(*act_id) != id)
+            .map(|(act_id, _)| act_id)
+        else {
+            return true;
+        };
+        let policy = world
+            .get_resource::<Config>()
+            .map(|config| config.duplicate_acts)
+            .unwrap_or_default();
+        match policy {
+            DuplicateActPolicy::Reject => {
+                error!("An act named '{}' already exists; not adding.", self.name);
+                false
+            }
+            DuplicateActPolicy::Replace => {
+                warn!("Replacing act named '{}'.", self.name);
+                world.despawn(existing);
+                if let Some(mut cache) = world.get_resource_mut::<NameActCache>() {
+                    cache.invalidate(None);
+                }
+                if let Some(mut cache) = world.get_resource_mut::<HotkeyActCache>() {
+                    cache.invalidate();
+                }
+                true
+            }
+            DuplicateActPolicy::Suffix => {
+                let names: Vec<Cow<'static, str>> =
+                    query.iter(world).map(|(_, act)| act.name.clone()).collect();
+                let name = (2..)
+                    .map(|i| format!("{}_{i}", self.name))
+                    .find(|name| !names.iter().any(|n| n == name))
+                    .expect("unused name");
+                self.name = name.into();
+                true
+            }
+        }
+    }
+
     /// Name the act.
     pub fn named(&mut self, name: impl Into<Cow<'static, str>>) -> &mut Self {
         self.name = name.into();
         self
     }
 
+    /// Add another name for the act, e.g., a short one.
+    pub fn alias(&mut self, name: impl Into<Cow<'static, str>>) -> &mut Self {
+        self.aliases.push(ActAlias::new(name));
+        self
+    }
+
+    /// Add a deprecated name for the act, e.g., its old name.
+    ///
+    /// It still runs the act but hints at the act's name the first time it is
+    /// used.
+    pub fn deprecated_alias(&mut self, name: impl Into<Cow<'static, str>>) -> &mut Self {
+        self.aliases.push(ActAlias::deprecated(name));
+        self
+    }
+
+    /// Put the act in a category.
+    ///
+    /// Acts are grouped by category in 'list_acts' and 'list_key_bindings',
+    /// and 'run_act' narrows its completions to a category given as a prefix
+    /// like "TapeActs/". Acts added with an [ActsPlugin] default to its type
+    /// name.
+    ///
+    /// [ActsPlugin]: crate::acts::ActsPlugin
+    pub fn category(&mut self, category: impl Into<Cow<'static, str>>) -> &mut Self {
+        self.category = Some(category.into());
+        self
+    }
+
+    /// Describe what the act does.
+    ///
+    /// The first line is its summary, shown by 'list_acts'. The whole
+    /// description is shown by 'describe_act'.
+    pub fn description(&mut self, description: impl Into<Cow<'static, str>>) -> &mut Self {
+        self.description = description.into();
+        self
+    }
+
     /// Bind a hotkey.
     pub fn bind<T>(&mut self, hotkey: impl IntoIterator<Item = T>) -> &mut Self
     where
@@ -160,6 +292,23 @@ impl ActBuilder {
         self
     }
 
+    /// Bind a hotkey in a keymap.
+    ///
+    /// It is only live while that keymap is enabled. See [Keymaps].
+    ///
+    /// [Keymaps]: crate::acts::keymaps::Keymaps
+    pub fn bind_in<T>(
+        &mut self,
+        keymap: impl Into<Cow<'static, str>>,
+        hotkey: impl IntoIterator<Item = T>,
+    ) -> &mut Self
+    where
+        KeyChord: From<T>,
+    {
+        self.hotkeys.push(Hotkey::new(hotkey).in_keymap(keymap));
+        self
+    }
+
     /// Bind a hotkey with an alias for that key sequence.
     ///
     /// ```no_compile
@@ -178,6 +327,20 @@ impl ActBuilder {
         self
     }
 
+    /// Only activate the act while `condition` holds, e.g.,
+    /// `in_state(GameState::Editor)`.
+    ///
+    /// It is checked every frame before key sequences are matched. While it
+    /// does not hold, the act is not [ActFlags::Active]: its key sequences do
+    /// not fire, and it does not complete in 'run_act'.
+    pub fn run_if<M>(&mut self, condition: impl Condition<M>) -> &mut Self {
+        let condition = IntoSystem::into_system(condition);
+        self.register_condition = Some(Box::new(move |world: &mut World| {
+            world.register_system(condition).entity()
+        }));
+        self
+    }
+
     /// Set flags.
     pub fn flags(&mut self, flags: ActFlags) -> &mut Self {
         self.flags = flags;
@@ -202,6 +365,8 @@ impl From<&mut ActBuilder> for ActBuilder {
         let taken: Cow<'static, str> = "*TAKEN*".into();
         Self {
             name: std::mem::replace(&mut builder.name, taken.clone()),
+            aliases: std::mem::take(&mut builder.aliases),
+            category: builder.category.take(),
             register_system: std::mem::replace(
                 &mut builder.register_system,
                 Box::new(|_world: &mut World| {
@@ -209,27 +374,36 @@ impl From<&mut ActBuilder> for ActBuilder {
                     Entity::PLACEHOLDER
                 }),
             ),
+            register_condition: builder.register_condition.take(),
             // system: builder.system.take(),
             hotkeys: std::mem::take(&mut builder.hotkeys),
             flags: builder.flags,
             shorten_name: builder.shorten_name,
             system_name: std::mem::replace(&mut builder.system_name, taken),
+            description: std::mem::take(&mut builder.description),
             input: builder.input.take(),
+            input_name: builder.input_name.take(),
         }
     }
 }
 
 impl Command for ActBuilder {
-    fn apply(self, world: &mut World) {
+    fn apply(mut self, world: &mut World) {
+        if !self.resolve_duplicate(None, world) {
+            return;
+        }
         let (act, id) = self.build(world);
         let name = Name::new(act.name.clone());
         let keyseqs = act.build_keyseqs(id, world);
+        let condition = act.condition;
         world.entity_mut(id).insert(act).insert(name);
 
         // let id = world.spawn(act).insert(name).id();
-        for keyseq_id in keyseqs {
+        for keyseq_id in keyseqs.into_iter().chain(condition) {
             world.entity_mut(keyseq_id).insert(ChildOf(id));
         }
+        warn_conflicts(id, world);
+        warn_alias_conflicts(id, world);
         let mut query = world.query_filtered::<Entity, With<ActContainer>>();
         if let Ok(act_container) = query.single(world) {
             world.entity_mut(id).insert(ChildOf(act_container));
@@ -238,18 +412,84 @@ impl Command for ActBuilder {
 }
 
 impl EntityCommand for ActBuilder {
-    fn apply(self, mut entity_world: EntityWorldMut) {
+    fn apply(mut self, mut entity_world: EntityWorldMut) {
         let id = entity_world.id();
 
         entity_world.world_scope(move |world: &mut World| {
+            if !self.resolve_duplicate(Some(id), world) {
+                return;
+            }
             let (act, system_id) = self.build(world);
             let keyseqs = act.build_keyseqs(id, world);
+            let condition = act.condition;
             let mut entity = world.get_entity_mut(id).unwrap();
             entity.insert(act);
             for keyseq_id in keyseqs {
                 world.entity_mut(keyseq_id).insert(ChildOf(id));
             }
-            world.entity_mut(system_id).insert(ChildOf(id));
+            for child in std::iter::once(system_id).chain(condition) {
+                world.entity_mut(child).insert(ChildOf(id));
+            }
+            warn_conflicts(id, world);
+            warn_alias_conflicts(id, world);
         });
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn act() {}
+
+    fn names(world: &mut World, policy: DuplicateActPolicy) -> Vec<String> {
+        world.insert_resource(Config {
+            duplicate_acts: policy,
+            ..default()
+        });
+        for _ in 0..3 {
+            Command::apply(ActBuilder::new(act), world);
+        }
+        let mut names: Vec<String> = world
+            .query::<&Act>()
+            .iter(world)
+            .map(|act| act.name.to_string())
+            .collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn duplicate_names() {
+        assert_eq!(
+            names(&mut World::new(), DuplicateActPolicy::Reject),
+            vec!["act"]
+        );
+        assert_eq!(
+            names(&mut World::new(), DuplicateActPolicy::Replace),
+            vec!["act"]
+        );
+        assert_eq!(
+            names(&mut World::new(), DuplicateActPolicy::Suffix),
+            vec!["act", "act_2", "act_3"]
+        );
+    }
+
+    #[test]
+    fn replace_despawns() {
+        let mut world = World::new();
+        let mut builder = ActBuilder::new(act);
+        builder.run_if(|| true);
+        Command::apply(builder, &mut world);
+        let (first, condition) = world
+            .query::<(Entity, &Act)>()
+            .single(&world)
+            .map(|(id, act)| (id, act.condition.unwrap()))
+            .unwrap();
+        Command::apply(ActBuilder::new(act), &mut world);
+        let second = world.query::<(Entity, &Act)>().single(&world).unwrap().0;
+        assert_ne!(first, second);
+        assert!(world.get_entity(first).is_err());
+        assert!(world.get_entity(condition).is_err());
+    }
+}
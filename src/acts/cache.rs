@@ -1,8 +1,11 @@
 //! Acts and their flags, builders, and collections
-use crate::acts::{Act, ActFlags, ActRef};
+use crate::acts::{keymaps::Keymaps, Act, ActFlags, ActRef};
 use bevy::prelude::*;
 use bevy_input_sequence::KeyChord;
-use std::collections::HashMap;
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap, HashSet},
+};
 use trie_rs::map::{Trie, TrieBuilder};
 
 pub(crate) fn plugin(app: &mut App) {
@@ -10,9 +13,34 @@ pub(crate) fn plugin(app: &mut App) {
         .init_resource::<NameActCache>();
 }
 
+/// Each category's name and a trie of its acts
+pub type CategoryTries = Vec<(Cow<'static, str>, Trie<u8, ActRef>)>;
+
 #[derive(Resource, Default)]
 pub struct NameActCache {
     trie: HashMap<ActFlags, Trie<u8, ActRef>>,
+    categories: HashMap<ActFlags, CategoryTries>,
+}
+
+/// Build a trie of the acts' names and aliases.
+///
+/// An alias that is another act's name or an alias already added is left out
+/// so each name maps to one act like [Act::find_by_name].
+fn build_names(acts: &[(Entity, &Act)]) -> Trie<u8, ActRef> {
+    let mut builder: TrieBuilder<u8, ActRef> = TrieBuilder::new();
+    let mut names: HashSet<&str> = HashSet::new();
+    for (id, act) in acts {
+        builder.push(act.name.as_ref(), ActRef::from_act(act, *id));
+        names.insert(act.name.as_ref());
+    }
+    for (id, act) in acts {
+        for alias in &act.aliases {
+            if names.insert(alias.name.as_ref()) {
+                builder.push(alias.name.as_ref(), ActRef::from_act(act, *id));
+            }
+        }
+    }
+    builder.build()
 }
 
 impl NameActCache {
@@ -25,13 +53,33 @@ impl NameActCache {
         flags: ActFlags,
     ) -> &Trie<u8, ActRef> {
         self.trie.entry(flags).or_insert_with(|| {
-            let mut builder: TrieBuilder<u8, ActRef> = TrieBuilder::new();
+            let acts: Vec<(Entity, &Act)> =
+                acts.filter(|(_, act)| act.flags.contains(flags)).collect();
+            build_names(&acts)
+        })
+    }
+
+    /// Retrieve the cached tries of each category's acts sorted by category.
+    /// Or if the cache has been invalidated, build and cache new tries using
+    /// the `acts` iterator.
+    pub fn category_tries<'a>(
+        &mut self,
+        acts: impl Iterator<Item = (Entity, &'a Act)>,
+        flags: ActFlags,
+    ) -> &[(Cow<'static, str>, Trie<u8, ActRef>)] {
+        self.categories.entry(flags).or_insert_with(|| {
+            let mut groups: BTreeMap<Cow<'static, str>, Vec<(Entity, &Act)>> = BTreeMap::new();
             for (id, act) in acts {
-                if act.flags.contains(flags) {
-                    builder.push(act.name.as_ref(), ActRef::from_act(act, id));
+                if let Some(ref category) = act.category {
+                    if act.flags.contains(flags) {
+                        groups.entry(category.clone()).or_default().push((id, act));
+                    }
                 }
             }
-            builder.build()
+            groups
+                .into_iter()
+                .map(|(category, acts)| (category, build_names(&acts)))
+                .collect()
         })
     }
 
@@ -39,16 +87,19 @@ impl NameActCache {
     pub fn invalidate(&mut self, flags: Option<ActFlags>) {
         if let Some(flags) = flags {
             self.trie.remove(&flags);
+            self.categories.remove(&flags);
         } else {
             self.trie.clear();
+            self.categories.clear();
         }
     }
 }
 
-/// Maps hotkeys to [Act]s
+/// Maps live hotkeys to [Act]s
 ///
 /// This is a trie of hotkeys for better performance and it is only updated when
-/// acts with hotkeys are added or removed.
+/// acts with hotkeys are added or removed, acts are activated or deactivated,
+/// or [Keymaps] change. See [Keymaps::live_hotkeys].
 #[derive(Resource, Default)]
 pub struct HotkeyActCache {
     trie: Option<Trie<KeyChord, ActRef>>,
@@ -61,13 +112,12 @@ impl HotkeyActCache {
     pub fn trie<'a>(
         &mut self,
         acts: impl Iterator<Item = (Entity, &'a Act)>,
+        keymaps: &Keymaps,
     ) -> &Trie<KeyChord, ActRef> {
         self.trie.get_or_insert_with(|| {
             let mut builder: TrieBuilder<KeyChord, ActRef> = TrieBuilder::new();
-            for (id, act) in acts {
-                for hotkey in &act.hotkeys {
-                    builder.insert(hotkey.chords.clone(), ActRef::from_act(act, id));
-                }
+            for (id, act, i) in keymaps.live_hotkeys(acts) {
+                builder.insert(act.hotkeys[i].chords.clone(), ActRef::from_act(act, id));
             }
             builder.build()
         })
@@ -0,0 +1,220 @@
+//! Detect key bindings that shadow one another
+use crate::{acts::Act, input::Hotkey};
+use bevy::prelude::*;
+use bevy_input_sequence::KeyChord;
+use std::{borrow::Cow, fmt};
+
+/// Two key bindings that cannot both be reached
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum Conflict {
+    /// Two acts bind the same key sequence.
+    #[error("{} is bound to both '{act}' and '{other}'.", Keys(.chords))]
+    Duplicate {
+        /// The key sequence
+        chords: Vec<KeyChord>,
+        /// First act
+        act: Cow<'static, str>,
+        /// Second act
+        other: Cow<'static, str>,
+    },
+    /// A key sequence of `act` is a strict prefix of a key sequence of `other`,
+    /// so `other` is never run by that binding.
+    #[error("{} of '{act}' shadows {} of '{other}'.", Keys(.prefix), Keys(.chords))]
+    Prefix {
+        /// The shorter key sequence
+        prefix: Vec<KeyChord>,
+        /// The act bound to the shorter key sequence
+        act: Cow<'static, str>,
+        /// The longer, shadowed key sequence
+        chords: Vec<KeyChord>,
+        /// The act bound to the longer key sequence
+        other: Cow<'static, str>,
+    },
+}
+
+/// Display key chords without the alias a [Hotkey] may have.
+struct Keys<'a>(&'a [KeyChord]);
+
+impl fmt::Display for Keys<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", Hotkey::new(self.0.iter().cloned()))
+    }
+}
+
+/// A hotkey of an act
+#[derive(Clone, Copy)]
+struct Binding<'a> {
+    id: Entity,
+    act: &'a Act,
+    chords: &'a [KeyChord],
+    keymap: Option<&'a str>,
+}
+
+impl Binding<'_> {
+    fn conflict(&self, other: &Binding) -> Option<Conflict> {
+        // Hotkeys in different keymaps shadow one another by design.
+        if self.chords.is_empty() || other.chords.is_empty() || self.keymap != other.keymap {
+            None
+        } else if self.chords == other.chords {
+            (self.id != other.id).then(|| Conflict::Duplicate {
+                chords: self.chords.to_vec(),
+                act: self.act.name.clone(),
+                other: other.act.name.clone(),
+            })
+        } else if other.chords.starts_with(self.chords) {
+            Some(Conflict::Prefix {
+                prefix: self.chords.to_vec(),
+                act: self.act.name.clone(),
+                chords: other.chords.to_vec(),
+                other: other.act.name.clone(),
+            })
+        } else if self.chords.starts_with(other.chords) {
+            other.conflict(self)
+        } else {
+            None
+        }
+    }
+}
+
+fn bindings<'a>(acts: impl Iterator<Item = (Entity, &'a Act)>) -> Vec<Binding<'a>> {
+    acts.flat_map(|(id, act)| {
+        act.hotkeys.iter().map(move |hotkey| Binding {
+            id,
+            act,
+            chords: &hotkey.chords,
+            keymap: hotkey.keymap.as_deref(),
+        })
+    })
+    .collect()
+}
+
+/// Find every pair of conflicting key bindings.
+pub fn find_conflicts<'a>(acts: impl Iterator<Item = (Entity, &'a Act)>) -> Vec<Conflict> {
+    let bindings = bindings(acts);
+    let mut conflicts = Vec::new();
+    for (i, a) in bindings.iter().enumerate() {
+        for b in &bindings[i + 1..] {
+            conflicts.extend(a.conflict(b));
+        }
+    }
+    conflicts
+}
+
+/// Find the conflicts that involve the key bindings of one act.
+pub fn find_conflicts_with<'a>(
+    act_id: Entity,
+    acts: impl Iterator<Item = (Entity, &'a Act)>,
+) -> Vec<Conflict> {
+    let bindings = bindings(acts);
+    let mut conflicts = Vec::new();
+    for (i, a) in bindings.iter().enumerate() {
+        for b in &bindings[i + 1..] {
+            if a.id == act_id || b.id == act_id {
+                conflicts.extend(a.conflict(b));
+            }
+        }
+    }
+    conflicts
+}
+
+/// Find the conflicts a new hotkey for an act would have.
+pub fn find_conflicts_for<'a>(
+    act_id: Entity,
+    hotkey: &Hotkey,
+    acts: impl Iterator<Item = (Entity, &'a Act)>,
+) -> Vec<Conflict> {
+    let acts: Vec<(Entity, &Act)> = acts.collect();
+    // The act may have no hotkeys yet, so it is not among the bindings.
+    let Some(act) = acts
+        .iter()
+        .find(|(id, _)| *id == act_id)
+        .map(|(_, act)| *act)
+    else {
+        return Vec::new();
+    };
+    let bindings = bindings(acts.into_iter());
+    let new = Binding {
+        id: act_id,
+        act,
+        chords: &hotkey.chords,
+        keymap: hotkey.keymap.as_deref(),
+    };
+    bindings.iter().filter_map(|b| new.conflict(b)).collect()
+}
+
+/// Warn about the conflicts that involve the key bindings of one act.
+pub(crate) fn warn_conflicts(act_id: Entity, world: &mut World) {
+    let mut query = world.query::<(Entity, &Act)>();
+    for conflict in find_conflicts_with(act_id, query.iter(world)) {
+        warn!("{conflict}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{acts::ActBuilder, prelude::keyseq};
+
+    fn help() {}
+    fn list_acts() {}
+    fn list_acts_again() {}
+    fn other() {}
+
+    #[test]
+    fn conflicts() {
+        let mut world = World::new();
+        let mut builder = ActBuilder::new(help);
+        builder.bind(keyseq! { Ctrl-H });
+        Command::apply(builder, &mut world);
+        let mut builder = ActBuilder::new(list_acts);
+        builder.bind(keyseq! { Ctrl-H A });
+        Command::apply(builder, &mut world);
+        let mut builder = ActBuilder::new(list_acts_again);
+        builder.bind(keyseq! { Ctrl-H A });
+        Command::apply(builder, &mut world);
+        let mut builder = ActBuilder::new(other);
+        builder.bind(keyseq! { Ctrl-O });
+        Command::apply(builder, &mut world);
+
+        let mut query = world.query::<(Entity, &Act)>();
+        let conflicts = find_conflicts(query.iter(&world));
+        assert_eq!(conflicts.len(), 3, "{conflicts:?}");
+        let messages: Vec<String> = conflicts.iter().map(|x| x.to_string()).collect();
+        assert!(messages.contains(&"Ctrl-H of 'help' shadows Ctrl-H A of 'list_acts'.".into()));
+        assert!(messages
+            .contains(&"Ctrl-H A is bound to both 'list_acts' and 'list_acts_again'.".into()));
+
+        let other_id = query
+            .iter(&world)
+            .find(|(_, act)| act.name == "other")
+            .map(|(id, _)| id)
+            .unwrap();
+        assert!(find_conflicts_with(other_id, query.iter(&world)).is_empty());
+        let hotkey: Hotkey = "Ctrl-H A B".parse().unwrap();
+        assert_eq!(
+            find_conflicts_for(other_id, &hotkey, query.iter(&world)).len(),
+            3
+        );
+    }
+
+    #[test]
+    fn conflicts_for_unbound_act() {
+        let mut world = World::new();
+        let mut builder = ActBuilder::new(help);
+        builder.bind(keyseq! { Ctrl-H });
+        Command::apply(builder, &mut world);
+        Command::apply(ActBuilder::new(other), &mut world);
+
+        let mut query = world.query::<(Entity, &Act)>();
+        let other_id = query
+            .iter(&world)
+            .find(|(_, act)| act.name == "other")
+            .map(|(id, _)| id)
+            .unwrap();
+        let hotkey: Hotkey = "Ctrl-H".parse().unwrap();
+        let conflicts = find_conflicts_for(other_id, &hotkey, query.iter(&world));
+        assert_eq!(conflicts.len(), 1, "{conflicts:?}");
+        assert!(Act::bind_hotkey(other_id, hotkey, &mut world).is_err());
+        assert!(find_conflicts(query.iter(&world)).is_empty());
+    }
+}
@@ -0,0 +1,159 @@
+//! Inline arguments for acts, e.g., `spawn_enemy 5 "goblin"`
+use crate::Error;
+use std::{any::type_name, fmt::Display, str::FromStr};
+
+/// Split a line into words separated by whitespace.
+///
+/// Double quotes group words and accept `\"` and `\\` escapes. Single quotes
+/// group words literally.
+///
+/// ```
+/// use bevy_minibuffer::acts::split_args;
+/// assert_eq!(
+///     split_args(r#"spawn_enemy 5 "goblin king""#).unwrap(),
+///     vec!["spawn_enemy", "5", "goblin king"]
+/// );
+/// ```
+pub fn split_args(line: &str) -> Result<Vec<String>, Error> {
+    let mut args = Vec::new();
+    let mut arg: Option<String> = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                let word = arg.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c) => word.push(c),
+                            None => break,
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err(Error::Message("Unterminated \" quote".into())),
+                    }
+                }
+            }
+            '\'' => {
+                let word = arg.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err(Error::Message("Unterminated ' quote".into())),
+                    }
+                }
+            }
+            c if c.is_whitespace() => args.extend(arg.take()),
+            c => arg.get_or_insert_with(String::new).push(c),
+        }
+    }
+    args.extend(arg);
+    Ok(args)
+}
+
+/// Parse an act's input from inline arguments
+///
+/// Implemented for primitives and `String`, which take one argument; tuples of
+/// those, which take one argument per field; and `Option`s, which are `None`
+/// without arguments.
+pub trait FromArgs: Sized {
+    /// Parse the arguments.
+    fn from_args(args: &[String]) -> Result<Self, Error>;
+}
+
+fn expect_args(args: &[String], count: usize) -> Result<(), Error> {
+    if args.len() == count {
+        Ok(())
+    } else {
+        let plural = if count == 1 { "" } else { "s" };
+        Err(Error::Message(
+            format!("Expected {count} argument{plural} but got {}.", args.len()).into(),
+        ))
+    }
+}
+
+fn parse_arg<T>(arg: &str) -> Result<T, Error>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    arg.parse().map_err(|e| {
+        Error::Message(format!("Could not parse {arg:?} as {}: {e}", type_name::<T>()).into())
+    })
+}
+
+macro_rules! impl_from_args {
+    ($($t:ty),*) => {
+        $(impl FromArgs for $t {
+            fn from_args(args: &[String]) -> Result<Self, Error> {
+                expect_args(args, 1)?;
+                parse_arg(&args[0])
+            }
+        })*
+    }
+}
+
+impl_from_args!(bool, char, String, f32, f64, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+macro_rules! impl_from_args_tuple {
+    ($count:expr; $($t:ident $i:tt),*) => {
+        impl<$($t),*> FromArgs for ($($t,)*)
+        where
+            $($t: FromStr, $t::Err: Display),*
+        {
+            fn from_args(args: &[String]) -> Result<Self, Error> {
+                expect_args(args, $count)?;
+                Ok(($(parse_arg::<$t>(&args[$i])?,)*))
+            }
+        }
+    }
+}
+
+impl_from_args_tuple!(2; A 0, B 1);
+impl_from_args_tuple!(3; A 0, B 1, C 2);
+impl_from_args_tuple!(4; A 0, B 1, C 2, D 3);
+
+impl<T: FromArgs> FromArgs for Option<T> {
+    fn from_args(args: &[String]) -> Result<Self, Error> {
+        if args.is_empty() {
+            Ok(None)
+        } else {
+            T::from_args(args).map(Some)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        split_args(line).unwrap()
+    }
+
+    #[test]
+    fn split() {
+        assert_eq!(args("  a  b "), vec!["a", "b"]);
+        assert_eq!(
+            args(r#"say "hi \"there\"" 'a\b' """#),
+            vec!["say", "hi \"there\"", "a\\b", ""]
+        );
+        assert_eq!(args(r#"x"y z"w"#), vec!["xy zw"]);
+        assert!(split_args("say \"hi").is_err());
+        assert!(args("").is_empty());
+    }
+
+    #[test]
+    fn from_args() {
+        assert_eq!(f32::from_args(&args("2.5")).unwrap(), 2.5);
+        assert!(f32::from_args(&args("two")).is_err());
+        assert!(f32::from_args(&args("1 2")).is_err());
+        assert_eq!(
+            <(u32, String)>::from_args(&args("5 \"goblin king\"")).unwrap(),
+            (5, "goblin king".into())
+        );
+        assert_eq!(Option::<i32>::from_args(&[]).unwrap(), None);
+        assert_eq!(Option::<i32>::from_args(&args("-3")).unwrap(), Some(-3));
+    }
+}
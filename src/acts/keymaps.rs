@@ -0,0 +1,348 @@
+//! Layered keymaps
+//!
+//! Every hotkey belongs to a keymap. Hotkeys bound without one are in the
+//! global keymap, which is always enabled. Other keymaps, like Emacs' modes,
+//! are enabled by pushing them onto the [Keymaps] resource. A hotkey in a
+//! keymap pushed later shadows one in a keymap pushed earlier or in the global
+//! keymap if either key sequence starts with the other, e.g., an "editor"
+//! `Ctrl-H` shadows a global `Ctrl-H` and a global `Ctrl-H A`.
+//!
+//! ```
+//! # use bevy::prelude::*;
+//! # use bevy_minibuffer::prelude::*;
+//! fn save_game() {}
+//! fn save_level() {}
+//!
+//! fn plugin(app: &mut App) {
+//!     app.add_acts((Act::new(save_game).bind(keyseq! { Ctrl-S }),
+//!                   Act::new(save_level).bind_in("editor", keyseq! { Ctrl-S })));
+//! }
+//!
+//! fn enter_editor(mut keymaps: ResMut<Keymaps>) {
+//!     keymaps.push("editor");
+//! }
+//! ```
+use crate::{
+    acts::{Act, ActFlags},
+    input::Hotkey,
+    plugin::MinibufferSet,
+};
+use bevy::prelude::*;
+use std::{borrow::Cow, collections::HashMap};
+
+pub(crate) fn plugin(app: &mut App) {
+    app.init_resource::<Keymaps>().add_systems(
+        Update,
+        respawn_keyseqs
+            .run_if(resource_changed::<Keymaps>.or(keymap_acts_added))
+            .in_set(MinibufferSet::Input),
+    );
+}
+
+/// The enabled keymaps in order of precedence, lowest first
+///
+/// The global keymap is always enabled and has the lowest precedence.
+#[derive(Resource, Debug, Default, Clone, PartialEq, Eq)]
+pub struct Keymaps {
+    enabled: Vec<Cow<'static, str>>,
+}
+
+impl Keymaps {
+    /// Enable a keymap with the highest precedence. If it was enabled, it is
+    /// moved to the top.
+    pub fn push(&mut self, keymap: impl Into<Cow<'static, str>>) {
+        let keymap = keymap.into();
+        self.remove(&keymap);
+        self.enabled.push(keymap);
+    }
+
+    /// Disable the keymap with the highest precedence and return it.
+    pub fn pop(&mut self) -> Option<Cow<'static, str>> {
+        self.enabled.pop()
+    }
+
+    /// Disable a keymap. Return true if it was enabled.
+    pub fn remove(&mut self, keymap: &str) -> bool {
+        let len = self.enabled.len();
+        self.enabled.retain(|k| k != keymap);
+        self.enabled.len() != len
+    }
+
+    /// Return the enabled keymaps, lowest precedence first.
+    pub fn enabled(&self) -> &[Cow<'static, str>] {
+        &self.enabled
+    }
+
+    /// Return the precedence of a keymap if it is enabled. The global keymap,
+    /// given as `None`, is 0.
+    pub fn precedence(&self, keymap: Option<&str>) -> Option<usize> {
+        match keymap {
+            None => Some(0),
+            Some(keymap) => self.enabled.iter().position(|k| k == keymap).map(|i| i + 1),
+        }
+    }
+
+    /// Return the hotkeys of active acts that are in an enabled keymap and not
+    /// shadowed by a hotkey in a keymap with higher precedence, i.e., one whose
+    /// key sequence starts with the other's or is a prefix of it.
+    ///
+    /// Each is given with its act and its index in the act's hotkeys.
+    pub fn live_hotkeys<'a>(
+        &self,
+        acts: impl Iterator<Item = (Entity, &'a Act)>,
+    ) -> Vec<(Entity, &'a Act, usize)> {
+        let hotkeys: Vec<(Entity, &Act, usize, usize)> = acts
+            .filter(|(_, act)| act.flags.contains(ActFlags::Active))
+            .flat_map(|(id, act)| {
+                act.hotkeys
+                    .iter()
+                    .enumerate()
+                    .filter_map(move |(i, hotkey)| {
+                        self.precedence(hotkey.keymap.as_deref())
+                            .map(|precedence| (id, act, i, precedence))
+                    })
+            })
+            .collect();
+        // Only hotkeys in an enabled keymap can shadow others.
+        let keymapped: Vec<_> = hotkeys
+            .iter()
+            .filter(|(_, _, _, precedence)| *precedence > 0)
+            .collect();
+        hotkeys
+            .iter()
+            .filter(|(_, act, i, precedence)| {
+                let chords = &act.hotkeys[*i].chords;
+                !keymapped.iter().any(|(_, other, j, other_precedence)| {
+                    let other_chords = &other.hotkeys[*j].chords;
+                    other_precedence > precedence
+                        && (other_chords.starts_with(chords) || chords.starts_with(other_chords))
+                })
+            })
+            .map(|(id, act, i, _)| (*id, *act, *i))
+            .collect()
+    }
+
+    /// Return true if hotkey `index` of act `act_id` is live. See
+    /// [Keymaps::live_hotkeys].
+    pub fn is_live<'a>(
+        &self,
+        act_id: Entity,
+        index: usize,
+        acts: impl Iterator<Item = (Entity, &'a Act)>,
+    ) -> bool {
+        self.live_hotkeys(acts)
+            .iter()
+            .any(|(id, _, i)| *id == act_id && *i == index)
+    }
+}
+
+impl Hotkey {
+    /// Put the hotkey in a keymap.
+    pub fn in_keymap(mut self, keymap: impl Into<Cow<'static, str>>) -> Self {
+        self.keymap = Some(keymap.into());
+        self
+    }
+
+    /// Return the key sequence without its alias and prefixed by its keymap if
+    /// it has one, e.g., "editor: Ctrl-S". It parses back into the same hotkey.
+    pub fn qualified(&self) -> String {
+        let keys = Hotkey::new(self.chords.iter().cloned());
+        match self.keymap {
+            Some(ref keymap) => format!("{keymap}: {keys}"),
+            None => keys.to_string(),
+        }
+    }
+}
+
+/// An extension to App to enable keymaps
+pub trait AddKeymaps {
+    /// Enable `keymap` while in `state`.
+    fn enable_keymap_in_state<S: States>(
+        &mut self,
+        keymap: impl Into<Cow<'static, str>>,
+        state: S,
+    ) -> &mut Self;
+}
+
+impl AddKeymaps for App {
+    fn enable_keymap_in_state<S: States>(
+        &mut self,
+        keymap: impl Into<Cow<'static, str>>,
+        state: S,
+    ) -> &mut Self {
+        let keymap = keymap.into();
+        let name = keymap.clone();
+        self.add_systems(
+            OnEnter(state.clone()),
+            move |mut keymaps: ResMut<Keymaps>| keymaps.push(keymap.clone()),
+        )
+        .add_systems(OnExit(state), move |mut keymaps: ResMut<Keymaps>| {
+            keymaps.remove(&name);
+        })
+    }
+}
+
+/// Return the indices of the live hotkeys of act `act_id`, which need not be
+/// spawned yet. Without a [Keymaps] resource, only the global keymap is
+/// enabled.
+pub(crate) fn live_indices(act_id: Entity, act: &Act, world: &World) -> Vec<usize> {
+    let default = Keymaps::default();
+    let keymaps = world.get_resource::<Keymaps>().unwrap_or(&default);
+    let mut query = world.try_query::<(Entity, &Act)>();
+    let others = query
+        .iter_mut()
+        .flat_map(|query| query.iter(world))
+        .filter(|(id, _)| *id != act_id);
+    keymaps
+        .live_hotkeys(others.chain(std::iter::once((act_id, act))))
+        .into_iter()
+        .filter(|(id, _, _)| *id == act_id)
+        .map(|(_, _, i)| i)
+        .collect()
+}
+
+/// Return the indices of the live hotkeys of every act. See [live_indices].
+pub(crate) fn all_live_indices(world: &World) -> HashMap<Entity, Vec<usize>> {
+    let default = Keymaps::default();
+    let keymaps = world.get_resource::<Keymaps>().unwrap_or(&default);
+    let mut live: HashMap<Entity, Vec<usize>> = HashMap::new();
+    if let Some(mut query) = world.try_query::<(Entity, &Act)>() {
+        for (id, _, i) in keymaps.live_hotkeys(query.iter(world)) {
+            live.entry(id).or_default().push(i);
+        }
+    }
+    live
+}
+
+/// Return true if any act has a hotkey in a keymap.
+pub(crate) fn in_use(world: &World) -> bool {
+    world.try_query::<&Act>().is_some_and(|mut query| {
+        query
+            .iter(world)
+            .any(|act| act.hotkeys.iter().any(|hotkey| hotkey.keymap.is_some()))
+    })
+}
+
+/// Return true if an act with a hotkey in a keymap was added.
+fn keymap_acts_added(acts: Query<&Act, Added<Act>>) -> bool {
+    acts.iter()
+        .any(|act| act.hotkeys.iter().any(|hotkey| hotkey.keymap.is_some()))
+}
+
+fn respawn_keyseqs(world: &mut World) {
+    Act::respawn_all_keyseqs(world);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{acts::ActBuilder, prelude::keyseq};
+
+    fn save_game() {}
+    fn save_level() {}
+
+    #[test]
+    fn precedence() {
+        let mut world = World::new();
+        let mut builder = ActBuilder::new(save_game);
+        builder.bind(keyseq! { Ctrl-S });
+        Command::apply(builder, &mut world);
+        let mut builder = ActBuilder::new(save_level);
+        builder
+            .bind_in("editor", keyseq! { Ctrl-S })
+            .bind_in("editor", keyseq! { Ctrl-L });
+        Command::apply(builder, &mut world);
+
+        let mut query = world.query::<(Entity, &Act)>();
+        let mut live = |keymaps: &Keymaps, world: &World| -> Vec<String> {
+            let mut live: Vec<String> = keymaps
+                .live_hotkeys(query.iter(world))
+                .into_iter()
+                .map(|(_, act, i)| format!("{} {}", act.hotkeys[i], act.name))
+                .collect();
+            live.sort();
+            live
+        };
+        let mut keymaps = Keymaps::default();
+        assert_eq!(live(&keymaps, &world), ["Ctrl-S save_game"]);
+        keymaps.push("editor");
+        assert_eq!(
+            live(&keymaps, &world),
+            ["Ctrl-L save_level", "Ctrl-S save_level"]
+        );
+        assert_eq!(keymaps.pop().as_deref(), Some("editor"));
+        assert!(!keymaps.remove("editor"));
+        assert_eq!(live(&keymaps, &world), ["Ctrl-S save_game"]);
+    }
+
+    fn help_a() {}
+    fn edit() {}
+    fn edit_a() {}
+
+    #[test]
+    fn prefix_shadows() {
+        let mut world = World::new();
+        let mut builder = ActBuilder::new(help_a);
+        builder.bind(keyseq! { Ctrl-H A });
+        Command::apply(builder, &mut world);
+        let mut builder = ActBuilder::new(edit);
+        builder.bind_in("editor", keyseq! { Ctrl-H });
+        Command::apply(builder, &mut world);
+        let mut builder = ActBuilder::new(save_game);
+        builder.bind(keyseq! { Ctrl-E });
+        Command::apply(builder, &mut world);
+        let mut builder = ActBuilder::new(edit_a);
+        builder.bind_in("editor", keyseq! { Ctrl-E A });
+        Command::apply(builder, &mut world);
+
+        let mut query = world.query::<(Entity, &Act)>();
+        let mut live = |keymaps: &Keymaps, world: &World| -> Vec<String> {
+            let mut live: Vec<String> = keymaps
+                .live_hotkeys(query.iter(world))
+                .into_iter()
+                .map(|(_, act, i)| format!("{} {}", act.hotkeys[i], act.name))
+                .collect();
+            live.sort();
+            live
+        };
+        let mut keymaps = Keymaps::default();
+        assert_eq!(
+            live(&keymaps, &world),
+            ["Ctrl-E save_game", "Ctrl-H A help_a"]
+        );
+        keymaps.push("editor");
+        assert_eq!(
+            live(&keymaps, &world),
+            ["Ctrl-E A edit_a", "Ctrl-H edit"],
+            "A shorter or longer key sequence in a keymap shadows a global one."
+        );
+    }
+
+    #[test]
+    fn inactive_without_keymaps() {
+        let mut world = World::new();
+        let mut builder = ActBuilder::new(save_game);
+        builder
+            .bind(keyseq! { Ctrl-S })
+            .bind_in("editor", keyseq! { Ctrl-E })
+            .sub_flags(ActFlags::Active);
+        Command::apply(builder, &mut world);
+        let mut builder = ActBuilder::new(save_level);
+        builder
+            .bind(keyseq! { Ctrl-L })
+            .bind_in("editor", keyseq! { Ctrl-E });
+        Command::apply(builder, &mut world);
+
+        let mut query = world.query::<(Entity, &Act)>();
+        for (id, act) in query.iter(&world) {
+            let live = live_indices(id, act, &world);
+            match act.name.as_ref() {
+                "save_game" => assert!(live.is_empty()),
+                _ => assert_eq!(live, [0]),
+            }
+        }
+        assert_eq!(all_live_indices(&world).len(), 1);
+        let mut keyseqs = world.query::<&bevy_input_sequence::input_sequence::KeySequence>();
+        assert_eq!(keyseqs.iter(&world).count(), 1);
+    }
+}
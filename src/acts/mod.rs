@@ -1,6 +1,9 @@
 //! Acts and their flags, builders, and collections
-use crate::{event::RunActEvent, input::Hotkey, ui::ActContainer};
-use bevy::{ecs::system::EntityCommand, prelude::*};
+use crate::{event::RunActEvent, input::Hotkey, plugin::MinibufferSet, ui::ActContainer, Error};
+use bevy::{
+    ecs::system::{EntityCommand, SystemId},
+    prelude::*,
+};
 use bevy_input_sequence::{action, input_sequence::KeySequence, KeyChord};
 use bitflags::bitflags;
 use std::{
@@ -18,6 +21,8 @@ mod collection;
 pub use collection::*;
 mod add_acts;
 pub use add_acts::AddActs;
+mod alias;
+pub use alias::*;
 mod plugin;
 pub use plugin::*;
 mod arg;
@@ -25,6 +30,11 @@ pub use arg::*;
 mod builder;
 pub use builder::*;
 pub mod cache;
+mod conflict;
+use cache::{HotkeyActCache, NameActCache};
+pub use conflict::*;
+mod inline_args;
+pub use inline_args::*;
 mod run_act;
 pub use run_act::*;
 
@@ -32,16 +42,57 @@ pub mod basic;
 #[cfg(feature = "async")]
 pub mod basic_async;
 
+pub mod binding;
+pub mod keymaps;
+pub mod resource;
+pub mod state;
 pub mod tape;
+pub mod undo;
 pub mod universal;
 
 pub(crate) fn plugin(app: &mut App) {
     app.register_type::<Act>()
         .add_plugins(tape::plugin)
         .add_plugins(universal::plugin)
+        .add_plugins(undo::plugin)
+        .add_plugins(keymaps::plugin)
         .add_plugins(cache::plugin)
         .add_plugins(run_act::plugin)
-        .add_systems(PostStartup, reparent_acts);
+        .init_resource::<DeprecationHints>()
+        .add_systems(PostStartup, reparent_acts)
+        .add_systems(Update, run_conditions.in_set(MinibufferSet::Input));
+}
+
+/// Activate or deactivate acts with a run condition.
+fn run_conditions(world: &mut World) {
+    let mut query = world.query::<(Entity, &Act)>();
+    let conditions: Vec<(Entity, Entity, bool)> = query
+        .iter(world)
+        .filter_map(|(id, act)| {
+            act.condition
+                .map(|condition| (id, condition, act.flags.contains(ActFlags::Active)))
+        })
+        .collect();
+    let mut changed = false;
+    for (id, condition, active) in conditions {
+        // Like Bevy's run conditions, one that cannot run does not hold.
+        let holds = world
+            .run_system(SystemId::<(), bool>::from_entity(condition))
+            .unwrap_or(false);
+        if holds == active {
+            continue;
+        }
+        if let Some(mut act) = world.get_mut::<Act>(id) {
+            act.flags.set(ActFlags::Active, holds);
+        }
+        Act::respawn_keyseqs(id, world);
+        changed = true;
+    }
+    if changed {
+        if let Some(mut cache) = world.get_resource_mut::<NameActCache>() {
+            cache.invalidate(None);
+        }
+    }
 }
 
 fn reparent_acts(
@@ -117,16 +168,25 @@ impl ActRef {
 pub struct Act {
     /// An act's name
     pub name: Cow<'static, str>,
+    /// Other names for the act
+    pub aliases: Vec<ActAlias>,
+    /// Category the act is grouped under
+    pub category: Option<Cow<'static, str>>,
     /// Hot keys
     pub hotkeys: Vec<Hotkey>,
     // What system runs when act is called
     // #[reflect(ignore)]
     pub(crate) system_id: Entity,
+    /// What condition system if any activates the act
+    pub(crate) condition: Option<Entity>,
     /// Flags for this act
     #[reflect(ignore)]
     pub flags: ActFlags,
     pub(crate) system_name: Cow<'static, str>,
+    /// Description of what the act does
+    pub description: Cow<'static, str>,
     pub(crate) input: Option<TypeId>,
+    pub(crate) input_name: Option<&'static str>,
 }
 impl Display for Act {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -152,32 +212,226 @@ impl Act {
         ActBuilder::new_with_input(system)
     }
 
-    /// Build the [KeySequence]s.
+    /// Build the [KeySequence]s of its live hotkeys. See
+    /// [Keymaps::live_hotkeys].
+    ///
+    /// [Keymaps::live_hotkeys]: keymaps::Keymaps::live_hotkeys
     pub fn build_keyseqs(&self, act_id: Entity, world: &mut World) -> Vec<Entity> {
-        self.hotkeys
+        let live = keymaps::live_indices(act_id, self, world);
+        spawn_keyseqs(
+            &self.hotkeys,
+            &live,
+            RunActEvent::from_act(self, act_id),
+            world,
+        )
+    }
+
+    /// Bind a hotkey to an act at runtime.
+    ///
+    /// A hotkey that conflicts with another binding is an error. Respawns the
+    /// act's [KeySequence]s and invalidates [HotkeyActCache].
+    pub fn bind_hotkey(act_id: Entity, hotkey: Hotkey, world: &mut World) -> Result<(), Error> {
+        let act = world
+            .get::<Act>(act_id)
+            .ok_or_else(|| Error::Message("No act for entity.".into()))?;
+        if act
+            .find_hotkey_in(hotkey.keymap.as_deref(), &hotkey.chords)
+            .is_some()
+        {
+            return Err(Error::Message(
+                format!("{} is already bound to {}.", hotkey.qualified(), act.name).into(),
+            ));
+        }
+        let mut query = world.query::<(Entity, &Act)>();
+        if let Some(conflict) = find_conflicts_for(act_id, &hotkey, query.iter(world))
+            .into_iter()
+            .next()
+        {
+            return Err(conflict.into());
+        }
+        let mut act = world
+            .get_mut::<Act>(act_id)
+            .ok_or_else(|| Error::Message("No act for entity.".into()))?;
+        act.hotkeys.push(hotkey);
+        Self::respawn_keyseqs(act_id, world);
+        Ok(())
+    }
+
+    /// Unbind a hotkey with the same key sequence and keymap from an act at
+    /// runtime and return it.
+    ///
+    /// Respawns the act's [KeySequence]s and invalidates [HotkeyActCache].
+    pub fn unbind_hotkey(
+        act_id: Entity,
+        hotkey: &Hotkey,
+        world: &mut World,
+    ) -> Result<Hotkey, Error> {
+        let mut act = world
+            .get_mut::<Act>(act_id)
+            .ok_or_else(|| Error::Message("No act for entity.".into()))?;
+        let index = act
+            .hotkeys
             .iter()
-            .enumerate()
-            .map(|(i, hotkey)| {
-                let name = Name::new(hotkey.to_string());
-                let id = world.spawn(name).id();
-                EntityCommand::apply(
-                    KeySequence::new(
-                        // XXX: Should this be trigger?
-                        action::send_event(RunActEvent::from_act(self, act_id).with_hotkey(i)),
-                        // action::trigger(RunActEvent::from_act(self, act_id).with_hotkey(i)),
-                        hotkey.chords.clone(),
-                    ),
-                    world.entity_mut(id),
-                );
-                id
+            .position(|h| h.chords == hotkey.chords && h.keymap == hotkey.keymap)
+            .ok_or_else(|| {
+                Error::Message(
+                    format!("{} is not bound to {}.", hotkey.qualified(), act.name).into(),
+                )
+            })?;
+        let hotkey = act.hotkeys.remove(index);
+        Self::respawn_keyseqs(act_id, world);
+        Ok(hotkey)
+    }
+
+    /// Replace all of an act's hotkeys at runtime.
+    ///
+    /// Conflicts are not checked; see [find_conflicts_with]. Respawns the
+    /// act's [KeySequence]s and invalidates [HotkeyActCache].
+    pub fn set_hotkeys(
+        act_id: Entity,
+        hotkeys: Vec<Hotkey>,
+        world: &mut World,
+    ) -> Result<(), Error> {
+        let mut act = world
+            .get_mut::<Act>(act_id)
+            .ok_or_else(|| Error::Message("No act for entity.".into()))?;
+        act.hotkeys = hotkeys;
+        Self::respawn_keyseqs(act_id, world);
+        Ok(())
+    }
+
+    /// Replace an act's [KeySequence]s with ones built from its hotkeys.
+    ///
+    /// If any hotkey is in a keymap, every act's are replaced since its
+    /// hotkeys may shadow or uncover those of other acts.
+    fn respawn_keyseqs(act_id: Entity, world: &mut World) {
+        if keymaps::in_use(world) {
+            Self::respawn_all_keyseqs(world);
+        } else {
+            Self::respawn_act_keyseqs(act_id, world);
+        }
+    }
+
+    /// Replace every act's [KeySequence]s.
+    pub(crate) fn respawn_all_keyseqs(world: &mut World) {
+        let mut live = keymaps::all_live_indices(world);
+        let mut query = world.query_filtered::<Entity, With<Act>>();
+        let ids: Vec<Entity> = query.iter(world).collect();
+        for id in ids {
+            let indices = live.remove(&id).unwrap_or_default();
+            Self::replace_keyseqs(id, &indices, world);
+        }
+    }
+
+    fn respawn_act_keyseqs(act_id: Entity, world: &mut World) {
+        let live = world
+            .get::<Act>(act_id)
+            .map(|act| keymaps::live_indices(act_id, act, world))
+            .unwrap_or_default();
+        Self::replace_keyseqs(act_id, &live, world);
+    }
+
+    /// Replace an act's [KeySequence]s with ones for its hotkeys at `live`
+    /// indices.
+    fn replace_keyseqs(act_id: Entity, live: &[usize], world: &mut World) {
+        let old: Vec<Entity> = world
+            .get::<Children>(act_id)
+            .map(|children| {
+                children
+                    .iter()
+                    .filter(|id| world.get::<KeySequence>(*id).is_some())
+                    .collect()
             })
-            .collect()
+            .unwrap_or_default();
+        for id in old {
+            world.despawn(id);
+        }
+        let Some(act) = world.get::<Act>(act_id) else {
+            return;
+        };
+        let hotkeys = act.hotkeys.clone();
+        let event = RunActEvent::from_act(act, act_id);
+        for id in spawn_keyseqs(&hotkeys, live, event, world) {
+            world.entity_mut(id).insert(ChildOf(act_id));
+        }
+        if let Some(mut cache) = world.get_resource_mut::<HotkeyActCache>() {
+            cache.invalidate();
+        }
+    }
+
+    /// Return true if the act has `name` as its name or an alias.
+    pub fn is_named(&self, name: &str) -> bool {
+        self.name == name || self.aliases.iter().any(|alias| alias.name == name)
+    }
+
+    /// Find the act named `name`.
+    ///
+    /// An act with `name` as its name is preferred over one with it as an
+    /// alias, so an alias cannot take over another act's name.
+    pub fn find_by_name<'a>(
+        acts: impl IntoIterator<Item = (Entity, &'a Act)>,
+        name: &str,
+    ) -> Option<(Entity, &'a Act)> {
+        let mut aliased = None;
+        for (id, act) in acts {
+            if act.name == name {
+                return Some((id, act));
+            }
+            if aliased.is_none() && act.is_named(name) {
+                aliased = Some((id, act));
+            }
+        }
+        aliased
+    }
+
+    /// Return the first line of the description.
+    pub fn summary(&self) -> &str {
+        self.description.lines().next().unwrap_or("")
+    }
+
+    /// Return the type name of the act's input if it accepts one.
+    pub fn input_type_name(&self) -> Option<&'static str> {
+        self.input_name
     }
 
     /// Find hotkey based on chords.
     pub fn find_hotkey(&self, chords: &[KeyChord]) -> Option<&Hotkey> {
         self.hotkeys.iter().find(|h| *h == chords)
     }
+
+    /// Find hotkey based on chords in a keymap, or the global keymap if `None`.
+    pub fn find_hotkey_in(&self, keymap: Option<&str>, chords: &[KeyChord]) -> Option<&Hotkey> {
+        self.hotkeys
+            .iter()
+            .find(|h| h.keymap.as_deref() == keymap && *h == chords)
+    }
+}
+
+/// Spawn a [KeySequence] for each hotkey that sends `event`.
+fn spawn_keyseqs(
+    hotkeys: &[Hotkey],
+    indices: &[usize],
+    event: RunActEvent,
+    world: &mut World,
+) -> Vec<Entity> {
+    indices
+        .iter()
+        .map(|&i| {
+            let hotkey = &hotkeys[i];
+            let name = Name::new(hotkey.to_string());
+            let id = world.spawn(name).id();
+            EntityCommand::apply(
+                KeySequence::new(
+                    // XXX: Should this be trigger?
+                    action::send_event(event.clone().with_hotkey(i)),
+                    // action::trigger(RunActEvent::from_act(self, act_id).with_hotkey(i)),
+                    hotkey.chords.clone(),
+                ),
+                world.entity_mut(id),
+            );
+            id
+        })
+        .collect()
 }
 
 impl AsRef<str> for Act {
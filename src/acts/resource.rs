@@ -0,0 +1,303 @@
+//! Show and set reflected resources
+//!
+//! Any resource registered with `#[reflect(Resource)]` may be shown or have
+//! its fields set by path, e.g., `Config.hide_delay`. Values are parsed as
+//! [RON](https://github.com/ron-rs/ron), so a `Duration` is written
+//! `(secs: 1, nanos: 0)` and an `Option<u32>` is written `Some(3)`.
+use crate::{
+    acts::{Act, ActFlags, Acts, ActsPlugin},
+    event::DispatchEvent,
+    prompt::{Submit, TextField},
+    Error, Minibuffer,
+};
+use bevy::{
+    ecs::reflect::{AppTypeRegistry, ReflectResource},
+    prelude::*,
+    reflect::{serde::TypedReflectDeserializer, GetPath, ReflectRef, TypeRegistry},
+};
+use serde::de::DeserializeSeed;
+
+/// How deep to complete field paths
+const MAX_DEPTH: usize = 4;
+
+/// Resource plugin and acts
+///
+/// Adds 'show_resource' and 'set_resource_field' acts.
+pub struct ResourceActs {
+    /// Acts
+    pub acts: Acts,
+}
+
+impl Default for ResourceActs {
+    fn default() -> Self {
+        Self {
+            acts: Acts::new([
+                Act::new(show_resource)
+                    .description("Show a reflected resource or one of its fields.")
+                    .add_flags(ActFlags::ShowMinibuffer),
+                Act::new(set_resource_field).description("Set a field of a reflected resource."),
+            ]),
+        }
+    }
+}
+
+impl Plugin for ResourceActs {
+    fn build(&self, _app: &mut App) {
+        self.warn_on_unused_acts();
+    }
+}
+
+impl ActsPlugin for ResourceActs {
+    fn acts(&self) -> &Acts {
+        &self.acts
+    }
+    fn acts_mut(&mut self) -> &mut Acts {
+        &mut self.acts
+    }
+}
+
+/// Return the name of every reflected resource in the world and the paths to
+/// their fields, e.g., "Config" and "Config.hide_delay", sorted.
+pub fn resource_paths(world: &World) -> Vec<String> {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let mut paths = Vec::new();
+    for registration in registry.iter() {
+        let Some(reflect_resource) = registration.data::<ReflectResource>() else {
+            continue;
+        };
+        let Ok(value) = reflect_resource.reflect(world) else {
+            continue;
+        };
+        let name = registration.type_info().type_path_table().short_path();
+        push_paths(name.to_string(), value.as_partial_reflect(), 0, &mut paths);
+    }
+    paths.sort();
+    paths
+}
+
+fn push_paths(path: String, value: &dyn PartialReflect, depth: usize, paths: &mut Vec<String>) {
+    if depth < MAX_DEPTH {
+        match value.reflect_ref() {
+            ReflectRef::Struct(s) => {
+                for i in 0..s.field_len() {
+                    if let (Some(name), Some(field)) = (s.name_at(i), s.field_at(i)) {
+                        push_paths(format!("{path}.{name}"), field, depth + 1, paths);
+                    }
+                }
+            }
+            ReflectRef::TupleStruct(s) => {
+                for (i, field) in s.iter_fields().enumerate() {
+                    push_paths(format!("{path}.{i}"), field, depth + 1, paths);
+                }
+            }
+            _ => {}
+        }
+    }
+    paths.push(path);
+}
+
+/// Split a path like "Config.hide_delay" into the resource's registration
+/// and the field path "hide_delay", which may be empty.
+fn resolve<'a, 'r>(
+    path: &'a str,
+    registry: &'r TypeRegistry,
+) -> Result<(&'r ReflectResource, &'a str), Error> {
+    let (name, field) = path.split_once('.').unwrap_or((path, ""));
+    registry
+        .iter()
+        .filter(|registration| {
+            let table = registration.type_info().type_path_table();
+            table.short_path() == name || table.path() == name
+        })
+        .find_map(|registration| registration.data::<ReflectResource>())
+        .map(|reflect_resource| (reflect_resource, field))
+        .ok_or_else(|| Error::Message(format!("No reflected resource named '{name}'.").into()))
+}
+
+fn no_field(path: &str) -> Error {
+    Error::Message(format!("No field at '{path}'.").into())
+}
+
+/// Return the value of a resource or one of its fields as text.
+pub fn show(path: &str, world: &World) -> Result<String, Error> {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let (reflect_resource, field) = resolve(path, &registry)?;
+    let value = reflect_resource
+        .reflect(world)
+        .map_err(|e| Error::Message(format!("{e}").into()))?;
+    let value = if field.is_empty() {
+        value.as_partial_reflect()
+    } else {
+        value.reflect_path(field).map_err(|_| no_field(path))?
+    };
+    Ok(format!("{path}: {value:#?}"))
+}
+
+/// Parse `input` as RON and set the field of a resource at `path` to it.
+pub fn set(path: &str, input: &str, world: &mut World) -> Result<(), Error> {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let (reflect_resource, field) = resolve(path, &registry)?;
+    // Parse before borrowing the resource mutably, which marks it changed.
+    let resource = reflect_resource
+        .reflect(&*world)
+        .map_err(|e| Error::Message(format!("{e}").into()))?;
+    let target = if field.is_empty() {
+        resource.as_partial_reflect()
+    } else {
+        resource.reflect_path(field).map_err(|_| no_field(path))?
+    };
+    let registration = target
+        .get_represented_type_info()
+        .and_then(|info| registry.get(info.type_id()))
+        .ok_or_else(|| Error::Message(format!("'{path}' is not registered.").into()))?;
+    let mut deserializer = ron::Deserializer::from_str(input)?;
+    let value =
+        TypedReflectDeserializer::new(registration, &registry).deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    let mut resource = reflect_resource
+        .reflect_mut(world)
+        .map_err(|e| Error::Message(format!("{e}").into()))?;
+    let target = if field.is_empty() {
+        resource.as_partial_reflect_mut()
+    } else {
+        resource
+            .reflect_path_mut(field)
+            .map_err(|_| no_field(path))?
+    };
+    target
+        .try_apply(&*value)
+        .map_err(|e| Error::Message(format!("{e}").into()))
+}
+
+/// Show a resource or one of its fields.
+pub fn show_resource(world: &mut World) {
+    let paths = resource_paths(world);
+    if let Err(e) = world.run_system_cached_with(prompt_show, paths) {
+        warn!("Could not prompt for resource: {e}");
+    }
+}
+
+fn prompt_show(In(paths): In<Vec<String>>, mut minibuffer: Minibuffer) {
+    minibuffer.prompt_lookup("Show resource: ", paths).observe(
+        |mut trigger: Trigger<Submit<String>>, mut commands: Commands| {
+            let Ok(path) = trigger.event_mut().take_result() else {
+                return;
+            };
+            commands.queue(move |world: &mut World| {
+                let msg = show(&path, world).unwrap_or_else(|e| e.to_string());
+                world.trigger(DispatchEvent::EmitMessage(msg));
+            });
+        },
+    );
+}
+
+/// Set a field of a resource.
+pub fn set_resource_field(world: &mut World) {
+    let paths = resource_paths(world);
+    if let Err(e) = world.run_system_cached_with(prompt_set, paths) {
+        warn!("Could not prompt for resource: {e}");
+    }
+}
+
+fn prompt_set(In(paths): In<Vec<String>>, mut minibuffer: Minibuffer) {
+    minibuffer.prompt_lookup("Set field: ", paths).observe(
+        |mut trigger: Trigger<Submit<String>>, mut minibuffer: Minibuffer| {
+            let Ok(path) = trigger.event_mut().take_result() else {
+                return;
+            };
+            minibuffer
+                .prompt::<TextField>(format!("Set {path} to: "))
+                .observe(
+                    move |mut trigger: Trigger<Submit<String>>, mut commands: Commands| {
+                        let Ok(input) = trigger.event_mut().take_result() else {
+                            return;
+                        };
+                        let path = path.clone();
+                        commands.queue(move |world: &mut World| {
+                            let msg = match set(&path, &input, world) {
+                                Ok(()) => format!("Set {path} to {input}."),
+                                Err(e) => e.to_string(),
+                            };
+                            world.trigger(DispatchEvent::EmitMessage(msg));
+                        });
+                    },
+                );
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[derive(Resource, Reflect, Default)]
+    #[reflect(Resource)]
+    struct Tuning {
+        speed: f32,
+        jump: Jump,
+        cooldown: Duration,
+    }
+
+    #[derive(Reflect, Default)]
+    struct Jump {
+        height: f32,
+        double: bool,
+    }
+
+    fn world() -> World {
+        let mut world = World::new();
+        world.init_resource::<AppTypeRegistry>();
+        world
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register::<Tuning>();
+        world.init_resource::<Tuning>();
+        world
+    }
+
+    #[test]
+    fn paths() {
+        assert_eq!(
+            resource_paths(&world()),
+            [
+                "Tuning",
+                "Tuning.cooldown",
+                "Tuning.jump",
+                "Tuning.jump.double",
+                "Tuning.jump.height",
+                "Tuning.speed"
+            ]
+        );
+    }
+
+    #[test]
+    fn set_fields() {
+        let mut world = world();
+        set("Tuning.speed", "2.5", &mut world).unwrap();
+        set("Tuning.jump.double", "true", &mut world).unwrap();
+        set("Tuning.cooldown", "(secs: 1, nanos: 0)", &mut world).unwrap();
+        let tuning = world.resource::<Tuning>();
+        assert_eq!(tuning.speed, 2.5);
+        assert!(tuning.jump.double);
+        assert_eq!(tuning.cooldown, Duration::from_secs(1));
+        assert_eq!(show("Tuning.speed", &world).unwrap(), "Tuning.speed: 2.5");
+
+        world.clear_trackers();
+        assert!(set("Tuning.speed", "fast", &mut world).is_err());
+        assert!(set("Tuning.speed", "3.5 junk", &mut world).is_err());
+        assert_eq!(world.resource::<Tuning>().speed, 2.5);
+        assert!(!world.is_resource_changed::<Tuning>());
+        assert_eq!(
+            set("Tuning.sped", "1.0", &mut world)
+                .unwrap_err()
+                .to_string(),
+            "No field at 'Tuning.sped'."
+        );
+        assert_eq!(
+            show("Tunning", &world).unwrap_err().to_string(),
+            "No reflected resource named 'Tunning'."
+        );
+    }
+}
@@ -1,5 +1,9 @@
 //! Acts and their flags, builders, and collections
-use bevy::{ecs::system::SystemId, prelude::*};
+use crate::{
+    acts::{Act, FromArgs},
+    Error,
+};
+use bevy::{ecs::system::SystemId, prelude::*, reflect::GetTypeRegistration};
 use std::{
     any::{Any, TypeId},
     collections::HashMap,
@@ -8,8 +12,12 @@ use std::{
         // Write
     },
     marker::PhantomData,
+    sync::Arc,
 };
 
+/// An act's input
+pub type Input = Arc<dyn Any + 'static + Send + Sync>;
+
 #[derive(Debug)]
 pub enum RunActError {
     CannotAcceptInput,
@@ -18,7 +26,8 @@ pub enum RunActError {
 }
 
 pub(crate) fn plugin(app: &mut App) {
-    app.init_resource::<RunActMap>();
+    app.init_resource::<RunActMap>()
+        .init_resource::<ArgParserMap>();
 }
 
 pub trait RunAct {
@@ -30,6 +39,16 @@ pub trait RunAct {
         world: &mut Commands,
     ) -> Result<(), RunActError>;
     fn debug_string(&self, input: &dyn Any) -> Option<String>;
+    /// Return the input as a reflected value if its type was registered with
+    /// [RunActMap::register_reflect].
+    fn reflect_input<'a>(&self, _input: &'a dyn Any) -> Option<&'a dyn PartialReflect> {
+        None
+    }
+    /// Convert a reflected value into an input if its type was registered with
+    /// [RunActMap::register_reflect].
+    fn input_from_reflect(&self, _value: &dyn PartialReflect) -> Option<Input> {
+        None
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -90,7 +109,11 @@ impl RunAct for ActSystem {
 }
 
 #[derive(Clone, Debug)]
-pub struct ActWithInputSystem<I: 'static>(PhantomData<fn(I)>);
+pub struct ActWithInputSystem<I: 'static> {
+    as_reflect: Option<fn(&I) -> &dyn PartialReflect>,
+    from_reflect: Option<fn(&dyn PartialReflect) -> Option<I>>,
+    _marker: PhantomData<fn(I)>,
+}
 
 impl<I: 'static> Default for ActWithInputSystem<I> {
     fn default() -> Self {
@@ -100,7 +123,25 @@ impl<I: 'static> Default for ActWithInputSystem<I> {
 
 impl<I: 'static> ActWithInputSystem<I> {
     pub fn new() -> Self {
-        Self(PhantomData)
+        Self {
+            as_reflect: None,
+            from_reflect: None,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<I: FromReflect> ActWithInputSystem<I> {
+    /// Create a runner whose inputs may be reflected.
+    pub fn new_reflect() -> Self {
+        fn as_reflect<I: PartialReflect>(input: &I) -> &dyn PartialReflect {
+            input
+        }
+        Self {
+            as_reflect: Some(as_reflect::<I>),
+            from_reflect: Some(I::from_reflect),
+            _marker: PhantomData,
+        }
     }
 }
 
@@ -144,7 +185,116 @@ where
             .downcast_ref::<I>()
             .map(|input: &I| format!("{:?}", input))
     }
+
+    fn reflect_input<'a>(&self, input: &'a dyn Any) -> Option<&'a dyn PartialReflect> {
+        self.as_reflect
+            .zip(input.downcast_ref::<I>())
+            .map(|(as_reflect, input)| as_reflect(input))
+    }
+
+    fn input_from_reflect(&self, value: &dyn PartialReflect) -> Option<Input> {
+        self.from_reflect
+            .and_then(|from_reflect| from_reflect(value))
+            .map(|input| Arc::new(input) as Input)
+    }
 }
 
 #[derive(Resource, Default, Deref, DerefMut)]
 pub struct RunActMap(HashMap<TypeId, Box<dyn RunAct + 'static + Send + Sync>>);
+
+impl RunActMap {
+    /// Register an act input type for reflection.
+    ///
+    /// Acts whose input type is registered may have their inputs saved and
+    /// loaded, e.g., with [crate::acts::tape::save_tapes]. The type must also
+    /// be registered with the [AppTypeRegistry]; [AddActs::register_act_input]
+    /// does both.
+    ///
+    /// [AddActs::register_act_input]: crate::acts::AddActs::register_act_input
+    pub fn register_reflect<I>(&mut self)
+    where
+        I: FromReflect + Clone + Default + Debug + Send + Sync,
+    {
+        self.insert(
+            TypeId::of::<I>(),
+            Box::new(ActWithInputSystem::<I>::new_reflect()),
+        );
+    }
+}
+
+/// Register an input type with the [RunActMap] and [AppTypeRegistry].
+pub(crate) fn register_act_input<I>(app: &mut App)
+where
+    I: FromReflect + GetTypeRegistration + Clone + Default + Debug + Send + Sync,
+{
+    app.register_type::<I>();
+    app.world_mut()
+        .get_resource_or_init::<RunActMap>()
+        .register_reflect::<I>();
+}
+
+/// Parses inline arguments into an act's input
+pub type ArgParser = Box<dyn Fn(&[String]) -> Result<Input, Error> + Send + Sync>;
+
+/// Maps an act's input type to its [ArgParser]
+///
+/// An act whose input type has a parser may be run with inline arguments,
+/// e.g., `spawn_enemy 5 "goblin"`. Primitives, `String`, and their `Option`s
+/// are registered by default.
+#[derive(Resource, Deref, DerefMut)]
+pub struct ArgParserMap(HashMap<TypeId, ArgParser>);
+
+impl Default for ArgParserMap {
+    fn default() -> Self {
+        let mut map = Self(HashMap::default());
+        macro_rules! register {
+            ($($t:ty),*) => {
+                $(map.register_from_args::<$t>();
+                  map.register_from_args::<Option<$t>>();)*
+            }
+        }
+        register!(bool, char, String, f32, f64, i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+        map
+    }
+}
+
+impl ArgParserMap {
+    /// Register a parser for an input type.
+    pub fn register<I>(
+        &mut self,
+        parser: impl Fn(&[String]) -> Result<I, Error> + Send + Sync + 'static,
+    ) where
+        I: Send + Sync + 'static,
+    {
+        self.insert(
+            TypeId::of::<I>(),
+            Box::new(move |args| parser(args).map(|input| Arc::new(input) as Input)),
+        );
+    }
+
+    /// Register an input type's [FromArgs] implementation as its parser.
+    pub fn register_from_args<I>(&mut self)
+    where
+        I: FromArgs + Send + Sync + 'static,
+    {
+        self.register(I::from_args);
+    }
+
+    /// Parse the arguments into an input for `act`.
+    pub fn parse(&self, act: &Act, args: &[String]) -> Result<Input, Error> {
+        let type_id = act.input.ok_or_else(|| {
+            Error::Message(format!("Act '{}' does not accept arguments.", act.name).into())
+        })?;
+        let parser = self.get(&type_id).ok_or_else(|| {
+            Error::Message(
+                format!(
+                    "Act '{}' has no argument parser for {}.",
+                    act.name,
+                    act.input_name.unwrap_or("its input")
+                )
+                .into(),
+            )
+        })?;
+        parser(args)
+    }
+}
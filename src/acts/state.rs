@@ -0,0 +1,155 @@
+//! Show and set [States]
+//!
+//! For a state type `GameState`, [StateActs] adds 'set_game_state', which
+//! prompts for one of its unit variants, and 'show_game_state'.
+use crate::{
+    acts::{Act, ActFlags, Acts, ActsPlugin},
+    autocomplete::Completed,
+    Minibuffer,
+};
+use bevy::{
+    prelude::*,
+    reflect::{TypePath, Typed},
+    state::state::FreelyMutableState,
+};
+use std::marker::PhantomData;
+
+/// State plugin and acts
+///
+/// Adds 'set_{state}' and 'show_{state}' acts where `{state}` is the type's
+/// name in snake case, e.g., 'set_game_state' for `GameState`.
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_minibuffer::prelude::*;
+/// #[derive(States, Reflect, Debug, Clone, PartialEq, Eq, Hash, Default)]
+/// enum GameState {
+///     #[default]
+///     Menu,
+///     Playing,
+///     Paused,
+/// }
+///
+/// fn plugin(app: &mut App) {
+///     app.init_state::<GameState>()
+///         .add_acts(StateActs::<GameState>::default());
+/// }
+/// ```
+pub struct StateActs<S> {
+    /// Acts
+    pub acts: Acts,
+    _state: PhantomData<fn() -> S>,
+}
+
+impl<S: FreelyMutableState + FromReflect + Typed + TypePath> Default for StateActs<S> {
+    fn default() -> Self {
+        let name = state_name::<S>();
+        let short = S::short_type_path();
+        Self {
+            acts: Acts::new([
+                Act::new(set_state::<S>)
+                    .named(format!("set_{name}"))
+                    .description(format!("Set the {short} state.")),
+                Act::new(show_state::<S>)
+                    .named(format!("show_{name}"))
+                    .description(format!("Show the {short} state."))
+                    .add_flags(ActFlags::ShowMinibuffer),
+            ]),
+            _state: PhantomData,
+        }
+    }
+}
+
+impl<S: States> Plugin for StateActs<S> {
+    fn build(&self, _app: &mut App) {
+        self.warn_on_unused_acts();
+    }
+}
+
+impl<S: States> ActsPlugin for StateActs<S> {
+    fn acts(&self) -> &Acts {
+        &self.acts
+    }
+    fn acts_mut(&mut self) -> &mut Acts {
+        &mut self.acts
+    }
+}
+
+/// Return the type's name in snake case, e.g., "game_state" for `GameState`
+/// and "hud_state" for `HUDState`.
+fn state_name<S: TypePath>() -> String {
+    let chars: Vec<char> = S::short_type_path()
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .collect();
+    let mut name = String::new();
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            // Split a word from a lowercase letter before it, or an acronym
+            // from a lowercase letter after it.
+            let prev = i.checked_sub(1).map(|j| chars[j]);
+            let next = chars.get(i + 1);
+            if prev.is_some_and(|p| !p.is_uppercase())
+                || (prev.is_some() && next.is_some_and(|n| n.is_lowercase()))
+            {
+                name.push('_');
+            }
+            name.extend(c.to_lowercase());
+        } else {
+            name.push(*c);
+        }
+    }
+    name
+}
+
+/// Prompt for a state and transition to it.
+pub fn set_state<S: FreelyMutableState + FromReflect + Typed + TypePath>(
+    mut minibuffer: Minibuffer,
+) {
+    minibuffer
+        .prompt_enum::<S>(format!("{}: ", S::short_type_path()))
+        .observe(
+            |mut trigger: Trigger<Completed<S>>,
+             next_state: Option<ResMut<NextState<S>>>,
+             mut minibuffer: Minibuffer| {
+                let Some(Ok(state)) = trigger.event_mut().take_result() else {
+                    return;
+                };
+                match next_state {
+                    Some(mut next_state) => {
+                        minibuffer.message(format!("Set {} to {state:?}.", S::short_type_path()));
+                        next_state.set(state);
+                    }
+                    None => minibuffer
+                        .message(format!("No state {} initialized.", S::short_type_path())),
+                }
+            },
+        );
+}
+
+/// Show the current state.
+pub fn show_state<S: States + TypePath>(state: Option<Res<State<S>>>, mut minibuffer: Minibuffer) {
+    minibuffer.message(match state {
+        Some(state) => format!("{}: {:?}", S::short_type_path(), state.get()),
+        None => format!("No state {} initialized.", S::short_type_path()),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(TypePath)]
+    struct GameState;
+    #[derive(TypePath)]
+    struct Screen;
+    #[derive(TypePath)]
+    struct HUDState;
+
+    #[test]
+    fn names() {
+        assert_eq!(state_name::<GameState>(), "game_state");
+        assert_eq!(state_name::<Screen>(), "screen");
+        assert_eq!(state_name::<HUDState>(), "hud_state");
+    }
+}
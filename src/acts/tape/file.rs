@@ -0,0 +1,293 @@
+//! Save and load tapes
+//!
+//! Tapes are written as RON. Acts are recorded by name rather than by entity,
+//! and inputs are serialized by reflection, so a tape file survives restarts.
+//!
+//! ```ron
+//! (
+//!     tapes: [
+//!         (
+//!             key: "A",
+//!             content: [
+//!                 (act: "set_color", input: Some(Some((red: 1.0, green: 0.0, blue: 0.0, alpha: 1.0)))),
+//!                 (act: "repeat", universal: Some(4)),
+//!             ],
+//!         ),
+//!     ],
+//! )
+//! ```
+use super::{RunActRecord, Tape, Tapes};
+use crate::{
+    acts::{universal::UniversalArg, Act, ActRef, RunActMap},
+    input::Hotkey,
+    Error,
+};
+use bevy::{
+    prelude::*,
+    reflect::{
+        serde::{TypedReflectDeserializer, TypedReflectSerializer},
+        TypeRegistry,
+    },
+};
+use ron::value::RawValue;
+use serde::{de::DeserializeSeed, Deserialize, Serialize};
+use std::path::PathBuf;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TapeFile {
+    tapes: Vec<TapeEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TapeEntry {
+    key: String,
+    content: Vec<RecordEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct RecordEntry {
+    act: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    input: Option<Box<RawValue>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    universal: Option<i32>,
+}
+
+impl Tapes {
+    /// Write tapes as RON.
+    ///
+    /// Records whose act no longer exists are skipped. An input whose type was
+    /// not registered with [AddActs::register_act_input] is an error.
+    ///
+    /// [AddActs::register_act_input]: crate::acts::AddActs::register_act_input
+    pub fn to_ron(
+        &self,
+        acts: &Query<&Act>,
+        run_act_map: &RunActMap,
+        registry: &TypeRegistry,
+    ) -> Result<String, Error> {
+        let mut file = TapeFile::default();
+        for (chord, tape) in self.iter() {
+            let mut content = Vec::new();
+            for record in &tape.content {
+                let Ok(act) = acts.get(record.act.id) else {
+                    warn!("Could not get act for {:?}", record.act.id);
+                    continue;
+                };
+                let input = match record.input {
+                    Some(ref input) => {
+                        let value = act
+                            .input
+                            .as_ref()
+                            .and_then(|type_id| run_act_map.get(type_id))
+                            .and_then(|run_act| run_act.reflect_input(&**input))
+                            .ok_or_else(|| {
+                                Error::Message(
+                                    format!("Input for act '{}' is not reflectable.", act.name)
+                                        .into(),
+                                )
+                            })?;
+                        Some(RawValue::from_rust(&TypedReflectSerializer::new(
+                            value, registry,
+                        ))?)
+                    }
+                    None => None,
+                };
+                content.push(RecordEntry {
+                    act: act.name.to_string(),
+                    input,
+                    universal: record.universal.0,
+                });
+            }
+            file.tapes.push(TapeEntry {
+                key: chord.to_string(),
+                content,
+            });
+        }
+        // Keep the output stable.
+        file.tapes.sort_by(|a, b| a.key.cmp(&b.key));
+        Ok(ron::ser::to_string_pretty(
+            &file,
+            ron::ser::PrettyConfig::default(),
+        )?)
+    }
+
+    /// Read tapes from RON.
+    ///
+    /// Acts are looked up by name. Unknown acts or inputs that cannot be
+    /// reconstructed are an error.
+    pub fn from_ron(
+        input: &str,
+        acts: &Query<(Entity, &Act)>,
+        run_act_map: &RunActMap,
+        registry: &TypeRegistry,
+    ) -> Result<Tapes, Error> {
+        let file: TapeFile = ron::from_str(input)?;
+        let mut tapes = Tapes::default();
+        for entry in file.tapes {
+            let hotkey: Hotkey = entry.key.parse()?;
+            let [chord] = &hotkey.chords[..] else {
+                return Err(Error::Message(
+                    format!("Tape key {:?} is not one key chord.", entry.key).into(),
+                ));
+            };
+            let mut tape = Tape::default();
+            for record in entry.content {
+                let (id, act) = Act::find_by_name(acts, &record.act).ok_or_else(|| {
+                    Error::Message(format!("No act named '{}' found.", record.act).into())
+                })?;
+                let input = match record.input {
+                    Some(raw) => {
+                        let not_reflectable = || {
+                            Error::Message(
+                                format!("Input for act '{}' is not reflectable.", act.name).into(),
+                            )
+                        };
+                        let type_id = act.input.ok_or_else(|| {
+                            Error::Message(
+                                format!("Act '{}' does not accept input.", act.name).into(),
+                            )
+                        })?;
+                        let registration = registry.get(type_id).ok_or_else(not_reflectable)?;
+                        let mut deserializer = ron::Deserializer::from_str(raw.get_ron())?;
+                        let value = TypedReflectDeserializer::new(registration, registry)
+                            .deserialize(&mut deserializer)?;
+                        let input = run_act_map
+                            .get(&type_id)
+                            .and_then(|run_act| run_act.input_from_reflect(&*value))
+                            .ok_or_else(not_reflectable)?;
+                        Some(input)
+                    }
+                    None => None,
+                };
+                tape.content.push(RunActRecord {
+                    act: ActRef::from_act(act, id),
+                    input,
+                    universal: UniversalArg(record.universal),
+                });
+            }
+            tapes.insert(chord.clone(), tape);
+        }
+        Ok(tapes)
+    }
+}
+
+/// Save all tapes to a file.
+///
+/// Returns the number of tapes saved.
+pub fn save_tapes(
+    In(path): In<PathBuf>,
+    tapes: Res<Tapes>,
+    acts: Query<&Act>,
+    run_act_map: Res<RunActMap>,
+    registry: Res<AppTypeRegistry>,
+) -> Result<usize, Error> {
+    let contents = tapes.to_ron(&acts, &run_act_map, &registry.read())?;
+    std::fs::write(path, contents)?;
+    Ok(tapes.len())
+}
+
+/// Load tapes from a file.
+///
+/// Tapes with the same key are replaced. Returns the number of tapes loaded.
+pub fn load_tapes(
+    In(path): In<PathBuf>,
+    mut tapes: ResMut<Tapes>,
+    acts: Query<(Entity, &Act)>,
+    run_act_map: Res<RunActMap>,
+    registry: Res<AppTypeRegistry>,
+) -> Result<usize, Error> {
+    let contents = std::fs::read_to_string(path)?;
+    let loaded = Tapes::from_ron(&contents, &acts, &run_act_map, &registry.read())?;
+    let count = loaded.len();
+    tapes.extend(loaded.0);
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        acts::{ActBuilder, ActFlags},
+        input::KeyChord,
+    };
+    use std::sync::Arc;
+
+    fn speed(In(_speed): In<f32>) {}
+
+    fn tapes_to_ron(
+        tapes: Res<Tapes>,
+        acts: Query<&Act>,
+        run_act_map: Res<RunActMap>,
+        registry: Res<AppTypeRegistry>,
+    ) -> Result<String, Error> {
+        tapes.to_ron(&acts, &run_act_map, &registry.read())
+    }
+
+    fn tapes_from_ron(
+        In(input): In<String>,
+        acts: Query<(Entity, &Act)>,
+        run_act_map: Res<RunActMap>,
+        registry: Res<AppTypeRegistry>,
+    ) -> Result<Tapes, Error> {
+        Tapes::from_ron(&input, &acts, &run_act_map, &registry.read())
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut world = World::new();
+        world.init_resource::<RunActMap>();
+        world.init_resource::<AppTypeRegistry>();
+        world.resource_mut::<RunActMap>().register_reflect::<f32>();
+        world
+            .resource::<AppTypeRegistry>()
+            .write()
+            .register::<f32>();
+        Command::apply(ActBuilder::new_with_input(speed), &mut world);
+        let (id, act) = world
+            .query::<(Entity, &Act)>()
+            .single(&world)
+            .map(|(id, act)| (id, ActRef::new(id, act.flags)))
+            .unwrap();
+        assert_eq!(act.flags, ActFlags::default());
+        let mut tapes = Tapes::default();
+        tapes.insert(
+            KeyChord::from(KeyCode::KeyA),
+            Tape {
+                content: vec![RunActRecord {
+                    act: ActRef::new(id, act.flags),
+                    input: Some(Arc::new(2.5f32)),
+                    universal: UniversalArg(Some(4)),
+                }],
+            },
+        );
+        world.insert_resource(tapes);
+
+        let ron = world.run_system_cached(tapes_to_ron).unwrap().unwrap();
+        assert!(ron.contains("act: \"speed\""), "{ron}");
+        let tapes = world
+            .run_system_cached_with(tapes_from_ron, ron)
+            .unwrap()
+            .unwrap();
+        let tape = tapes.get(&KeyChord::from(KeyCode::KeyA)).unwrap();
+        let record = &tape.content[0];
+        assert_eq!(record.act.id, id);
+        assert_eq!(record.universal.0, Some(4));
+        assert_eq!(
+            record.input.as_ref().and_then(|x| x.downcast_ref::<f32>()),
+            Some(&2.5)
+        );
+    }
+
+    #[test]
+    fn unknown_act() {
+        let mut world = World::new();
+        world.init_resource::<RunActMap>();
+        world.init_resource::<AppTypeRegistry>();
+        let ron = r#"(tapes: [(key: "A", content: [(act: "missing")])])"#;
+        let result = world
+            .run_system_cached_with(tapes_from_ron, ron.into())
+            .unwrap();
+        assert!(result.is_err());
+    }
+}
@@ -1,22 +1,27 @@
 use crate::{
     acts::{
-        universal::UniversalArg, Act, ActFlags, ActRef, ActSystem, Acts, ActsPlugin, RunActMap,
+        undo::UndoStack, universal::UniversalArg, Act, ActFlags, ActRef, ActSystem, Acts,
+        ActsPlugin, RunActMap,
     },
     event::{KeyChordEvent, LastRunAct, RunActEvent},
     input::{keyseq, KeyChord},
+    prompt::{Submit, TextField},
     Minibuffer,
 };
 use bevy::prelude::*;
 #[cfg(feature = "clipboard")]
 use copypasta::{ClipboardContext, ClipboardProvider};
 use std::{
-    any::Any,
     borrow::Cow,
     collections::HashMap,
     fmt::{self, Debug, Write},
+    path::PathBuf,
     sync::Arc,
 };
 
+mod file;
+pub use file::*;
+
 pub(crate) fn plugin(app: &mut App) {
     app.init_resource::<TapeRecorder>()
         .init_resource::<RunActMap>()
@@ -39,14 +44,14 @@ pub struct RunActRecord {
     pub universal: UniversalArg,
 }
 
-pub type Input = Arc<dyn Any + 'static + Send + Sync>;
+pub use crate::acts::Input;
 
 impl From<&RunActEvent> for RunActRecord {
     fn from(e: &RunActEvent) -> Self {
         Self {
             act: e.act,
             // hotkey: e.hotkey.clone(),
-            input: None,
+            input: e.input.clone(),
             universal: UniversalArg(None),
         }
     }
@@ -65,22 +70,47 @@ pub struct Tape {
 
 pub struct TapeActs {
     acts: Acts,
+    preload: Vec<PathBuf>,
+}
+
+impl TapeActs {
+    /// Load tapes from a file at startup.
+    ///
+    /// Act inputs must be registered with [AddActs::register_act_input] to be
+    /// loaded.
+    ///
+    /// [AddActs::register_act_input]: crate::acts::AddActs::register_act_input
+    pub fn preload(mut self, path: impl Into<PathBuf>) -> Self {
+        self.preload.push(path.into());
+        self
+    }
 }
 
 impl Default for TapeActs {
     fn default() -> Self {
         Self {
+            preload: Vec::new(),
             acts: Acts::new([
                 Act::new(tape_record)
+                    .description("Start or stop recording a tape.")
                     .bind(keyseq! { Q })
                     .sub_flags(ActFlags::Record),
-                Act::new_with_input(tape_play).bind_aliased(keyseq! { Shift-2 }, "@"),
+                Act::new_with_input(tape_play)
+                    .description("Play a tape.")
+                    .bind_aliased(keyseq! { Shift-2 }, "@"),
                 // .sub_flags(ActFlags::Record),
                 // Act::new(repeat).bind(keyseq! { Period }).sub_flags(ActFlags::Record | ActFlags::RunAct),
                 Act::new(repeat)
+                    .description("Repeat the last act.")
                     .bind(keyseq! { Period })
                     .sub_flags(ActFlags::Record),
-                &mut Act::new(tape_copy),
+                Act::new(tape_copy).description("Print a tape as Rust code."),
+                Act::new(tape_save)
+                    .description("Save tapes to a file.")
+                    .sub_flags(ActFlags::Record),
+                Act::new(tape_load)
+                    .description("Load tapes from a file.")
+                    .sub_flags(ActFlags::Record),
             ]),
         }
     }
@@ -453,6 +483,18 @@ mod fun {
 impl Plugin for TapeActs {
     fn build(&self, app: &mut App) {
         app.init_resource::<Tapes>().init_resource::<LastPlayed>();
+        if !self.preload.is_empty() {
+            let paths = self.preload.clone();
+            app.add_systems(PostStartup, move |world: &mut World| {
+                for path in &paths {
+                    match world.run_system_cached_with(load_tapes, path.clone()) {
+                        Ok(Ok(count)) => info!("Loaded {count} tapes from {path:?}."),
+                        Ok(Err(e)) => warn!("Could not load tapes from {path:?}: {e}"),
+                        Err(e) => warn!("Could not run load_tapes: {e}"),
+                    }
+                }
+            });
+        }
 
         self.warn_on_unused_acts();
     }
@@ -554,6 +596,16 @@ impl TapeRecorder {
             _ => (),
         }
     }
+
+    /// Return the record of the last act run unless a tape is playing.
+    pub fn last_record(&self) -> Option<&RunActRecord> {
+        match self {
+            TapeRecorder::Record { tape, .. } | TapeRecorder::Off { one_off: tape } => {
+                tape.content.last()
+            }
+            TapeRecorder::Play => None,
+        }
+    }
 }
 
 fn tape_record(
@@ -644,12 +696,9 @@ fn tape_play(
             let tape = tape.clone();
             let count = 1; // TODO: Need to store universal somewhere.
                            // next_tape_state.set(SoundState::Play);
+            let record = minibuffer.current_record();
             commands.queue(move |world: &mut World| {
-                for _ in 0..count {
-                    if let Err(e) = world.run_system_cached_with(play_tape_sys, &tape) {
-                        warn!("Error playing tape: {e:?}");
-                    }
-                }
+                play_tape_as_unit(&tape, count, format!("tape {chord}"), record, world);
             });
         } else {
             // next_tape_state.set(SoundState::Load);
@@ -691,12 +740,10 @@ fn tape_play(
                     if let Some(tape) = tapes.get(&chord) {
                         let tape = tape.clone();
                         tape_state.set(SoundState::Play);
+                        let record = minibuffer.current_record();
+                        let description = format!("tape {chord}");
                         commands.queue(move |world: &mut World| {
-                            for _ in 0..count {
-                                if let Err(e) = world.run_system_cached_with(play_tape_sys, &tape) {
-                                    warn!("Error playing tape: {e:?}");
-                                }
-                            }
+                            play_tape_as_unit(&tape, count, description, record, world);
                         });
                         **last_played = Some(chord);
                     } else {
@@ -772,6 +819,41 @@ fn tape_copy(mut minibuffer: Minibuffer, mut tape_state: ResMut<NextState<SoundS
     );
 }
 
+fn tape_save(mut minibuffer: Minibuffer) {
+    minibuffer.prompt::<TextField>("Save tapes to: ").observe(
+        |mut trigger: Trigger<Submit<String>>, mut commands: Commands| {
+            if let Ok(path) = trigger.event_mut().take_result() {
+                commands.queue(move |world: &mut World| {
+                    let msg = match world.run_system_cached_with(save_tapes, PathBuf::from(&path)) {
+                        Ok(Ok(count)) => format!("Saved {count} tapes to {path}."),
+                        Ok(Err(e)) => format!("Could not save tapes: {e}"),
+                        Err(e) => format!("Could not run save_tapes: {e}"),
+                    };
+                    world.trigger(crate::event::DispatchEvent::EmitMessage(msg));
+                });
+            }
+        },
+    );
+}
+
+fn tape_load(mut minibuffer: Minibuffer, mut tape_state: ResMut<NextState<SoundState>>) {
+    tape_state.set(SoundState::Load);
+    minibuffer.prompt::<TextField>("Load tapes from: ").observe(
+        |mut trigger: Trigger<Submit<String>>, mut commands: Commands| {
+            if let Ok(path) = trigger.event_mut().take_result() {
+                commands.queue(move |world: &mut World| {
+                    let msg = match world.run_system_cached_with(load_tapes, PathBuf::from(&path)) {
+                        Ok(Ok(count)) => format!("Loaded {count} tapes from {path}."),
+                        Ok(Err(e)) => format!("Could not load tapes: {e}"),
+                        Err(e) => format!("Could not run load_tapes: {e}"),
+                    };
+                    world.trigger(crate::event::DispatchEvent::EmitMessage(msg));
+                });
+            }
+        },
+    );
+}
+
 fn repeat(
     tape_recorder: Res<TapeRecorder>,
     universal_arg: Res<UniversalArg>,
@@ -864,6 +946,26 @@ impl Tape {
 //     }
 // }
 
+/// Play a tape `count` times and group the inverses its acts push onto the
+/// [UndoStack], so it is undone as a unit. Redoing it replays `record`.
+fn play_tape_as_unit(
+    tape: &Tape,
+    count: i32,
+    description: String,
+    record: Option<RunActRecord>,
+    world: &mut World,
+) {
+    let mark = world.resource::<UndoStack>().mark();
+    for _ in 0..count {
+        if let Err(e) = world.run_system_cached_with(play_tape_sys, tape) {
+            warn!("Error playing tape: {e:?}");
+        }
+    }
+    world
+        .resource_mut::<UndoStack>()
+        .group(mark, description, record);
+}
+
 pub fn play_tape_sys(
     InRef(tape): InRef<Tape>,
     mut commands: Commands,
@@ -0,0 +1,337 @@
+//! Undo and redo reversible acts
+//!
+//! Acts opt in by pushing an inverse with [Minibuffer::push_undo]. The 'undo'
+//! act runs the inverses of the most recent acts; the 'redo' act runs those
+//! acts again. Both take the [UniversalArg] as a repeat count.
+//!
+//! The inverses pushed while a tape plays are grouped, so a played tape is
+//! undone and redone as a unit.
+use crate::{
+    acts::{
+        tape::{play_tape_sys, RunActRecord, Tape},
+        universal::UniversalArg,
+        Act, Acts, ActsPlugin,
+    },
+    event::DispatchEvent,
+};
+use bevy::prelude::*;
+use std::{borrow::Cow, fmt};
+
+pub(crate) fn plugin(app: &mut App) {
+    // Like UniversalArg, acts may push undos whether UndoActs is added or not.
+    app.init_resource::<UndoStack>();
+}
+
+/// Undo plugin and acts
+///
+/// Adds 'undo' and 'redo' acts.
+pub struct UndoActs {
+    /// Acts
+    pub acts: Acts,
+}
+
+impl Default for UndoActs {
+    fn default() -> Self {
+        Self {
+            acts: Acts::new([
+                Act::new(undo).description("Undo the last act."),
+                Act::new(redo).description("Redo the last undone act."),
+            ]),
+        }
+    }
+}
+
+impl Plugin for UndoActs {
+    fn build(&self, _app: &mut App) {
+        self.warn_on_unused_acts();
+    }
+}
+
+impl ActsPlugin for UndoActs {
+    fn acts(&self) -> &Acts {
+        &self.acts
+    }
+    fn acts_mut(&mut self) -> &mut Acts {
+        &mut self.acts
+    }
+}
+
+/// An act's inverse
+pub struct UndoEntry {
+    /// What is undone, e.g., "teleport"
+    pub description: Cow<'static, str>,
+    /// The run of the act that pushed it, which 'redo' replays
+    pub record: Option<RunActRecord>,
+    undo: Box<dyn FnOnce(&mut World) + Send + Sync>,
+}
+
+impl fmt::Debug for UndoEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UndoEntry")
+            .field("description", &self.description)
+            .field("record", &self.record)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An undone act that may be redone
+#[derive(Debug, Clone)]
+pub struct RedoEntry {
+    /// What is redone
+    pub description: Cow<'static, str>,
+    /// The run of the act to replay
+    pub record: RunActRecord,
+}
+
+/// The undo and redo stacks
+///
+/// NOTE: This resource is always present even if [UndoActs] has not been
+/// added, so acts can support undo without requiring it.
+#[derive(Resource, Debug)]
+pub struct UndoStack {
+    undos: Vec<UndoEntry>,
+    redos: Vec<RedoEntry>,
+    /// The record being redone
+    redoing: Option<RunActRecord>,
+    /// Number of inverses dropped for exceeding `max_len`
+    dropped: usize,
+    /// Maximum number of inverses kept
+    pub max_len: usize,
+}
+
+impl Default for UndoStack {
+    fn default() -> Self {
+        Self {
+            undos: Vec::new(),
+            redos: Vec::new(),
+            redoing: None,
+            dropped: 0,
+            max_len: 100,
+        }
+    }
+}
+
+impl UndoStack {
+    /// Push an inverse. Unless an act is being redone, this clears the redo
+    /// stack. The oldest inverses past `max_len` are dropped.
+    pub fn push(
+        &mut self,
+        description: impl Into<Cow<'static, str>>,
+        record: Option<RunActRecord>,
+        undo: impl Command + Sync,
+    ) {
+        let record = match self.redoing {
+            Some(ref redoing) => Some(redoing.clone()),
+            None => {
+                self.redos.clear();
+                record
+            }
+        };
+        self.undos.push(UndoEntry {
+            description: description.into(),
+            record,
+            undo: Box::new(move |world: &mut World| undo.apply(world)),
+        });
+        if self.undos.len() > self.max_len {
+            let excess = self.undos.len() - self.max_len;
+            self.undos.drain(..excess);
+            self.dropped += excess;
+        }
+    }
+
+    /// Return a mark of the inverses pushed so far for [UndoStack::group].
+    pub fn mark(&self) -> usize {
+        self.dropped + self.undos.len()
+    }
+
+    /// Replace the inverses pushed since `mark` with one that runs them in
+    /// reverse, so they are undone as a unit. Those dropped for exceeding
+    /// `max_len` are not included.
+    pub fn group(
+        &mut self,
+        mark: usize,
+        description: impl Into<Cow<'static, str>>,
+        record: Option<RunActRecord>,
+    ) {
+        let start = mark.saturating_sub(self.dropped);
+        if start >= self.undos.len() {
+            return;
+        }
+        let entries: Vec<UndoEntry> = self.undos.drain(start..).collect();
+        self.push(description, record, move |world: &mut World| {
+            for entry in entries.into_iter().rev() {
+                (entry.undo)(world);
+            }
+        });
+    }
+
+    /// Pop the most recent inverse.
+    pub fn pop_undo(&mut self) -> Option<UndoEntry> {
+        self.undos.pop()
+    }
+
+    /// Pop the most recently undone act.
+    pub fn pop_redo(&mut self) -> Option<RedoEntry> {
+        self.redos.pop()
+    }
+
+    /// Return the inverses, oldest first.
+    pub fn undos(&self) -> &[UndoEntry] {
+        &self.undos
+    }
+
+    /// Return the undone acts, oldest first.
+    pub fn redos(&self) -> &[RedoEntry] {
+        &self.redos
+    }
+
+    /// Clear both stacks.
+    pub fn clear(&mut self) {
+        self.undos.clear();
+        self.redos.clear();
+    }
+
+    /// Add input logged after the inverse was pushed to its record.
+    pub(crate) fn ammend_input(&mut self, record: Option<&RunActRecord>) {
+        let (Some(entry), Some(record)) = (self.undos.last_mut(), record) else {
+            return;
+        };
+        if let Some(ref mut entry_record) = entry.record {
+            if entry_record.act.id == record.act.id && entry_record.input.is_none() {
+                entry_record.input.clone_from(&record.input);
+            }
+        }
+    }
+}
+
+/// Run the inverse of the last act, or the last N acts given a universal
+/// argument. A negative argument redoes.
+pub fn undo(universal_arg: Res<UniversalArg>, mut commands: Commands) {
+    let count = universal_arg.unwrap_or(1);
+    commands.queue(move |world: &mut World| {
+        if count < 0 {
+            redo_n(count.unsigned_abs(), world);
+        } else {
+            undo_n(count.unsigned_abs(), world);
+        }
+    });
+}
+
+/// Run the last undone act again, or the last N given a universal argument. A
+/// negative argument undoes.
+pub fn redo(universal_arg: Res<UniversalArg>, mut commands: Commands) {
+    let count = universal_arg.unwrap_or(1);
+    commands.queue(move |world: &mut World| {
+        if count < 0 {
+            undo_n(count.unsigned_abs(), world);
+        } else {
+            redo_n(count.unsigned_abs(), world);
+        }
+    });
+}
+
+fn undo_n(count: u32, world: &mut World) {
+    let mut undone = Vec::new();
+    for _ in 0..count {
+        let mut undo_stack = world.resource_mut::<UndoStack>();
+        let Some(entry) = undo_stack.pop_undo() else {
+            break;
+        };
+        if let Some(record) = entry.record {
+            undo_stack.redos.push(RedoEntry {
+                description: entry.description.clone(),
+                record,
+            });
+        }
+        (entry.undo)(world);
+        undone.push(entry.description);
+    }
+    world.trigger(DispatchEvent::EmitMessage(if undone.is_empty() {
+        "Nothing to undo.".into()
+    } else {
+        format!("Undo {}.", undone.join(", "))
+    }));
+}
+
+fn redo_n(count: u32, world: &mut World) {
+    let mut redone = Vec::new();
+    for _ in 0..count {
+        let Some(entry) = world.resource_mut::<UndoStack>().pop_redo() else {
+            break;
+        };
+        let tape = Tape {
+            content: vec![entry.record.clone()],
+        };
+        world.resource_mut::<UndoStack>().redoing = Some(entry.record);
+        // Replay it once, not by the redo's own universal argument.
+        world.resource_mut::<UniversalArg>().0 = None;
+        if let Err(e) = world.run_system_cached_with(play_tape_sys, &tape) {
+            warn!("Error redoing '{}': {e:?}", entry.description);
+        }
+        world.resource_mut::<UniversalArg>().0 = None;
+        world.resource_mut::<UndoStack>().redoing = None;
+        redone.push(entry.description);
+    }
+    world.trigger(DispatchEvent::EmitMessage(if redone.is_empty() {
+        "Nothing to redo.".into()
+    } else {
+        format!("Redo {}.", redone.join(", "))
+    }));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Resource, Default)]
+    struct Position(i32);
+
+    #[test]
+    fn undo_without_record() {
+        let mut world = World::new();
+        world.init_resource::<UndoStack>();
+        world.init_resource::<UniversalArg>();
+        world.init_resource::<Position>();
+        let mut undo_stack = world.resource_mut::<UndoStack>();
+        undo_stack.push("step", None, |world: &mut World| {
+            world.resource_mut::<Position>().0 -= 1;
+        });
+        undo_stack.push("step", None, |world: &mut World| {
+            world.resource_mut::<Position>().0 -= 1;
+        });
+        world.resource_mut::<Position>().0 = 2;
+        world.insert_resource(UniversalArg(Some(3)));
+        world.run_system_cached(undo).unwrap();
+        assert_eq!(world.resource::<Position>().0, 0);
+        let undo_stack = world.resource::<UndoStack>();
+        assert!(undo_stack.undos().is_empty());
+        // Nothing to replay without a record.
+        assert!(undo_stack.redos().is_empty());
+    }
+
+    #[test]
+    fn max_len() {
+        let mut world = World::new();
+        world.init_resource::<Position>();
+        let mut undo_stack = UndoStack {
+            max_len: 3,
+            ..default()
+        };
+        let step = |world: &mut World| world.resource_mut::<Position>().0 -= 1;
+        undo_stack.push("step 1", None, step);
+        let mark = undo_stack.mark();
+        for description in ["step 2", "step 3", "step 4"] {
+            undo_stack.push(description, None, step);
+        }
+        let descriptions: Vec<&str> = undo_stack
+            .undos()
+            .iter()
+            .map(|entry| entry.description.as_ref())
+            .collect();
+        assert_eq!(descriptions, ["step 2", "step 3", "step 4"]);
+        undo_stack.group(mark, "steps", None);
+        assert_eq!(undo_stack.undos().len(), 1);
+        (undo_stack.pop_undo().unwrap().undo)(&mut world);
+        assert_eq!(world.resource::<Position>().0, -3);
+    }
+}
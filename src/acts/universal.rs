@@ -52,6 +52,7 @@ impl Default for UniversalArgActs {
                 // Act::new(universal_arg.pipe(sink::future))
                 Act::new(universal_arg)
                     .named("universal_arg")
+                    .description("Pass a numeric argument to the next act.")
                     .bind(keyseq! { Ctrl-U })
                     .sub_flags(ActFlags::RunAct | ActFlags::Record),
             ]),
@@ -65,6 +66,7 @@ impl UniversalArgActs {
         self.acts.push(
             Act::new(display_universal_arg)
                 .named("display_universal_arg")
+                .description("Show the universal argument.")
                 .add_flags(ActFlags::ShowMinibuffer),
         );
         self
@@ -80,6 +82,7 @@ impl UniversalArgActs {
         self.acts.push(
             Act::new(universal_arg_async.pipe(sink::future))
                 .named("universal_arg")
+                .description("Pass a numeric argument to the next act.")
                 .bind(keyseq! { Ctrl-U })
                 .sub_flags(ActFlags::RunAct),
         );
@@ -139,6 +142,7 @@ pub fn display_universal_arg(arg: Res<UniversalArg>, mut minibuffer: Minibuffer)
 /// [UniversalArgActs] plugin has not been added. This is to enable users to
 /// opt-in to universal argument acts while allowing act writers to support universal arguments if available.
 #[derive(Debug, Clone, Resource, Default, Reflect, Deref, DerefMut)]
+#[reflect(Resource)]
 pub struct UniversalArg(pub Option<i32>);
 
 fn universal_arg(
@@ -0,0 +1,137 @@
+//! Case-insensitive matching
+use super::{lookup::iter_to_error, Lookup, LookupError, LookupMap};
+
+/// Wrap a [Lookup] to ignore case.
+///
+/// Completions and resolutions always use the canonical-cased key of the
+/// wrapped lookup. If the input matches a key except for case, looking it up
+/// returns that key as [LookupError::OneMatch] so the prompt's text is
+/// corrected.
+///
+/// ```
+/// use bevy_minibuffer::autocomplete::{CaseInsensitive, Lookup, LookupError, LookupMap};
+/// let lookup = CaseInsensitive::new(vec!["John", "Sean", "Shane"]);
+/// assert!(matches!(lookup.lookup("joh"), Err(LookupError::OneMatch(s)) if s == "John"));
+/// assert_eq!(lookup.resolve("john"), Some("John".into()));
+/// ```
+///
+/// [FuzzyLookup](super::FuzzyLookup) already ignores case and need not be
+/// wrapped.
+#[derive(Debug, Clone)]
+pub struct CaseInsensitive<L> {
+    lookup: L,
+}
+
+impl<L> CaseInsensitive<L> {
+    /// Wrap a lookup.
+    pub fn new(lookup: L) -> Self {
+        Self { lookup }
+    }
+
+    /// Return the wrapped lookup.
+    pub fn into_inner(self) -> L {
+        self.lookup
+    }
+}
+
+impl<L: Lookup> CaseInsensitive<L> {
+    /// Return the canonical keys that start with `input` ignoring case.
+    fn matches(&self, input: &str) -> Vec<String> {
+        let input = input.to_lowercase();
+        self.lookup
+            .all_lookups("")
+            .into_iter()
+            .filter(|word| word.to_lowercase().starts_with(&input))
+            .collect()
+    }
+}
+
+impl<L: Lookup> Lookup for CaseInsensitive<L> {
+    fn lookup(&self, input: &str) -> Result<(), LookupError> {
+        if self.lookup.lookup(input).is_ok() {
+            return Ok(());
+        }
+        let matches = self.matches(input);
+        let mut exact = matches
+            .iter()
+            .filter(|word| word.to_lowercase() == input.to_lowercase());
+        if let Some(word) = exact.next() {
+            if exact.next().is_none() {
+                return Err(LookupError::OneMatch(word.clone()));
+            }
+        }
+        Err(iter_to_error(matches.into_iter()))
+    }
+
+    fn longest_prefix(&self, input: &str) -> Option<String> {
+        let matches = self.matches(input);
+        let (first, rest) = matches.split_first()?;
+        let first: Vec<char> = first.chars().collect();
+        let mut len = first.len();
+        for word in rest {
+            len = first
+                .iter()
+                .zip(word.chars())
+                .take(len)
+                .take_while(|(a, b)| a.to_lowercase().eq(b.to_lowercase()))
+                .count();
+        }
+        Some(first[..len].iter().collect())
+    }
+
+    fn all_lookups(&self, input: &str) -> Vec<String> {
+        self.matches(input)
+    }
+
+    fn highlights(&self, input: &str, candidate: &str) -> Vec<usize> {
+        self.lookup.highlights(input, candidate)
+    }
+}
+
+impl<L: LookupMap> LookupMap for CaseInsensitive<L> {
+    type Item = L::Item;
+
+    fn resolve(&self, input: &str) -> Option<Self::Item> {
+        match self.lookup(input) {
+            Ok(()) => self.lookup.resolve(input),
+            Err(LookupError::OneMatch(word)) => self.lookup.resolve(&word),
+            Err(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn case_insensitive_lookup() {
+        let lookup = CaseInsensitive::new(vec!["John", "Sean", "Shane"]);
+        assert!(lookup.lookup("John").is_ok());
+        assert!(matches!(lookup.lookup("JOHN"), Err(LookupError::OneMatch(s)) if s == "John"));
+        assert!(matches!(lookup.lookup("sh"), Err(LookupError::OneMatch(s)) if s == "Shane"));
+        assert!(matches!(lookup.lookup("s"), Err(LookupError::ManyMatches)));
+        assert!(matches!(lookup.lookup("x"), Err(LookupError::NoMatch)));
+        assert_eq!(lookup.all_lookups("s"), vec!["Sean", "Shane"]);
+    }
+
+    #[test]
+    fn case_insensitive_longest_prefix() {
+        let lookup = CaseInsensitive::new(vec!["Shane", "shawn", "Sean"]);
+        assert_eq!(lookup.longest_prefix("sh"), Some("Sha".into()));
+        assert_eq!(lookup.longest_prefix("S"), Some("S".into()));
+        assert_eq!(lookup.longest_prefix("x"), None);
+    }
+
+    #[test]
+    fn case_insensitive_resolve() {
+        let mut map = HashMap::new();
+        map.insert("John", 1);
+        map.insert("JOHNNY", 2);
+        let lookup = CaseInsensitive::new(map);
+        assert_eq!(lookup.resolve("john"), Some(1));
+        assert_eq!(lookup.resolve("johnny"), Some(2));
+        assert_eq!(lookup.resolve("jo"), None);
+    }
+}
@@ -0,0 +1,142 @@
+//! Narrow completions to a category
+use super::{Lookup, LookupError, LookupMap};
+use std::borrow::Cow;
+
+/// Wrap a [Lookup] so that a `category/` prefix narrows it to that category.
+///
+/// Input without a known category prefix is looked up in the wrapped lookup.
+///
+/// ```
+/// use bevy_minibuffer::autocomplete::{Categorized, Lookup, LookupMap};
+/// let lookup = Categorized::new(vec!["bind_key", "tape_play", "tape_record"])
+///     .with_category("TapeActs", vec!["tape_play", "tape_record"]);
+/// assert_eq!(lookup.all_lookups("TapeActs/"), vec!["TapeActs/tape_play", "TapeActs/tape_record"]);
+/// assert!(lookup.lookup("TapeActs/bind_key").is_err());
+/// assert_eq!(lookup.resolve("TapeActs/tape_play"), Some("tape_play".into()));
+/// assert_eq!(lookup.resolve("bind_key"), Some("bind_key".into()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Categorized<L> {
+    lookup: L,
+    categories: Vec<(Cow<'static, str>, L)>,
+}
+
+impl<L> Categorized<L> {
+    /// Wrap a lookup.
+    pub fn new(lookup: L) -> Self {
+        Self {
+            lookup,
+            categories: Vec::new(),
+        }
+    }
+
+    /// Add a category with a lookup of its own.
+    pub fn with_category(mut self, category: impl Into<Cow<'static, str>>, lookup: L) -> Self {
+        self.categories.push((category.into(), lookup));
+        self
+    }
+
+    /// Return the wrapped lookup.
+    pub fn into_inner(self) -> L {
+        self.lookup
+    }
+
+    /// Split `input` into its category, the category's lookup, and the rest
+    /// if it starts with a known category.
+    fn split<'a>(&self, input: &'a str) -> Option<(&str, &L, &'a str)> {
+        let (category, rest) = input.split_once('/')?;
+        self.categories
+            .iter()
+            .find(|(name, _)| name == category)
+            .map(|(name, lookup)| (name.as_ref(), lookup, rest))
+    }
+}
+
+impl<L: Lookup> Lookup for Categorized<L> {
+    fn lookup(&self, input: &str) -> Result<(), LookupError> {
+        match self.split(input) {
+            Some((category, lookup, rest)) => lookup.lookup(rest).map_err(|e| match e {
+                LookupError::OneMatch(word) => LookupError::OneMatch(format!("{category}/{word}")),
+                e => e,
+            }),
+            None => self.lookup.lookup(input),
+        }
+    }
+
+    fn longest_prefix(&self, input: &str) -> Option<String> {
+        match self.split(input) {
+            Some((category, lookup, rest)) => lookup
+                .longest_prefix(rest)
+                .map(|prefix| format!("{category}/{prefix}")),
+            None => self.lookup.longest_prefix(input),
+        }
+    }
+
+    fn all_lookups(&self, input: &str) -> Vec<String> {
+        match self.split(input) {
+            Some((category, lookup, rest)) => lookup
+                .all_lookups(rest)
+                .into_iter()
+                .map(|word| format!("{category}/{word}"))
+                .collect(),
+            None => self.lookup.all_lookups(input),
+        }
+    }
+
+    fn highlights(&self, input: &str, candidate: &str) -> Vec<usize> {
+        match self.split(input) {
+            Some((category, lookup, rest)) => {
+                let Some(word) = candidate
+                    .strip_prefix(category)
+                    .and_then(|word| word.strip_prefix('/'))
+                else {
+                    return Vec::new();
+                };
+                let offset = category.chars().count() + 1;
+                lookup
+                    .highlights(rest, word)
+                    .into_iter()
+                    .map(|i| i + offset)
+                    .collect()
+            }
+            None => self.lookup.highlights(input, candidate),
+        }
+    }
+}
+
+impl<L: LookupMap> LookupMap for Categorized<L> {
+    type Item = L::Item;
+
+    fn resolve(&self, input: &str) -> Option<Self::Item> {
+        match self.split(input) {
+            Some((_, lookup, rest)) => lookup.resolve(rest),
+            None => self.lookup.resolve(input),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn categorized_lookup() {
+        let lookup = Categorized::new(vec!["bind_key", "tape_play", "tape_record"])
+            .with_category("TapeActs", vec!["tape_play", "tape_record"]);
+        assert!(matches!(
+            lookup.lookup("TapeActs/tape_p"),
+            Err(LookupError::OneMatch(s)) if s == "TapeActs/tape_play"
+        ));
+        assert!(matches!(
+            lookup.lookup("TapeActs/b"),
+            Err(LookupError::NoMatch)
+        ));
+        assert!(lookup.lookup("tape_play").is_ok());
+        assert_eq!(
+            lookup.longest_prefix("TapeActs/t"),
+            Some("TapeActs/tape_".into())
+        );
+        assert_eq!(lookup.all_lookups("Other/"), Vec::<String>::new());
+        assert_eq!(lookup.resolve("TapeActs/bind_key"), None);
+    }
+}
@@ -0,0 +1,104 @@
+//! Complete the first word and leave the rest as arguments
+use super::{Lookup, LookupError, LookupMap};
+
+/// Wrap a [Lookup] to only look up the first word of the input.
+///
+/// The words after it are arguments and are not completed. This lets a prompt
+/// accept a line like `spawn_enemy 5 "goblin"` while still completing
+/// `spawn_enemy`.
+///
+/// ```
+/// use bevy_minibuffer::autocomplete::{CommandLine, Lookup, LookupMap};
+/// let lookup = CommandLine::new(vec!["spawn_enemy", "spawn_tree"]);
+/// assert!(lookup.lookup("spawn_enemy 5").is_ok());
+/// assert!(lookup.lookup("spawn 5").is_err());
+/// assert_eq!(lookup.resolve("spawn_tree big"), Some("spawn_tree".into()));
+/// ```
+#[derive(Debug, Clone)]
+pub struct CommandLine<L> {
+    lookup: L,
+}
+
+impl<L> CommandLine<L> {
+    /// Wrap a lookup.
+    pub fn new(lookup: L) -> Self {
+        Self { lookup }
+    }
+
+    /// Return the wrapped lookup.
+    pub fn into_inner(self) -> L {
+        self.lookup
+    }
+}
+
+/// Split `input` into its first word and the rest if there is a rest.
+fn split_first_word(input: &str) -> Option<(&str, &str)> {
+    let input = input.trim_start();
+    input
+        .find(char::is_whitespace)
+        .map(|index| input.split_at(index))
+}
+
+impl<L: Lookup> Lookup for CommandLine<L> {
+    fn lookup(&self, input: &str) -> Result<(), LookupError> {
+        match split_first_word(input) {
+            Some((word, rest)) => self.lookup.lookup(word).map_err(|e| match e {
+                LookupError::OneMatch(word) => LookupError::OneMatch(format!("{word}{rest}")),
+                e => e,
+            }),
+            None => self.lookup.lookup(input),
+        }
+    }
+
+    fn longest_prefix(&self, input: &str) -> Option<String> {
+        match split_first_word(input) {
+            Some(_) => None,
+            None => self.lookup.longest_prefix(input),
+        }
+    }
+
+    fn all_lookups(&self, input: &str) -> Vec<String> {
+        match split_first_word(input) {
+            Some(_) => Vec::new(),
+            None => self.lookup.all_lookups(input),
+        }
+    }
+
+    fn highlights(&self, input: &str, candidate: &str) -> Vec<usize> {
+        self.lookup.highlights(input, candidate)
+    }
+}
+
+impl<L: LookupMap> LookupMap for CommandLine<L> {
+    type Item = L::Item;
+
+    fn resolve(&self, input: &str) -> Option<Self::Item> {
+        match split_first_word(input) {
+            Some((word, _)) => self.lookup.resolve(word),
+            None => self.lookup.resolve(input),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn command_line_lookup() {
+        let lookup = CommandLine::new(vec!["spawn_enemy", "spawn_tree"]);
+        assert!(matches!(
+            lookup.lookup("spawn_e 5 \"goblin\""),
+            Err(LookupError::OneMatch(s)) if s == "spawn_enemy 5 \"goblin\""
+        ));
+        assert!(matches!(lookup.lookup("x 5"), Err(LookupError::NoMatch)));
+        assert_eq!(lookup.longest_prefix("spawn_"), Some("spawn_".into()));
+        assert_eq!(lookup.longest_prefix("spawn_e 5"), None);
+        assert_eq!(
+            lookup.all_lookups("spawn"),
+            vec!["spawn_enemy", "spawn_tree"]
+        );
+        assert!(lookup.all_lookups("spawn_tree ").is_empty());
+        assert_eq!(lookup.resolve("spawn_enemy"), Some("spawn_enemy".into()));
+    }
+}
@@ -0,0 +1,151 @@
+//! Pick an entity by name
+use super::{lookup::iter_to_error, Lookup, LookupError, LookupMap};
+use bevy::{ecs::query::QueryFilter, prelude::*};
+use std::collections::HashMap;
+
+/// Look up entities by their [Name].
+///
+/// Entities that share a name are told apart by their index, e.g., "goblin#7"
+/// and "goblin#9". It resolves to an [Entity], so it can be given to
+/// `prompt_map()` of either [Minibuffer] or `MinibufferAsync`.
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_minibuffer::prelude::*;
+/// #[derive(Component)]
+/// struct Enemy;
+///
+/// fn despawn_enemy(enemies: Query<(Entity, &Name), With<Enemy>>,
+///                  mut minibuffer: Minibuffer) {
+///     minibuffer
+///         .prompt_map("Despawn: ", EntityLookup::from_query(&enemies))
+///         .observe(|mut trigger: Trigger<Completed<Entity>>, mut commands: Commands| {
+///             if let Some(Ok(id)) = trigger.event_mut().take_result() {
+///                 commands.entity(id).despawn();
+///             }
+///         });
+/// }
+/// ```
+///
+/// [Minibuffer]: crate::Minibuffer
+#[derive(Debug, Clone, Default)]
+pub struct EntityLookup {
+    /// Labels and their entities sorted by label
+    entities: Vec<(String, Entity)>,
+}
+
+impl EntityLookup {
+    /// Create a lookup from entities and their names.
+    pub fn new<'a>(entities: impl IntoIterator<Item = (Entity, &'a Name)>) -> Self {
+        let entities: Vec<(Entity, &Name)> = entities.into_iter().collect();
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for (_, name) in &entities {
+            *counts.entry(name.as_str()).or_default() += 1;
+        }
+        let mut entities: Vec<(String, Entity)> = entities
+            .iter()
+            .map(|(id, name)| {
+                let label = if counts[name.as_str()] > 1 {
+                    format!("{}#{}", name.as_str(), id.index())
+                } else {
+                    name.to_string()
+                };
+                (label, *id)
+            })
+            .collect();
+        entities.sort();
+        Self { entities }
+    }
+
+    /// Create a lookup from a query, which may be filtered, e.g., `Query<(Entity,
+    /// &Name), With<Enemy>>`.
+    pub fn from_query<F: QueryFilter>(query: &Query<(Entity, &Name), F>) -> Self {
+        Self::new(query.iter())
+    }
+
+    /// Return the label of an entity if it is present.
+    pub fn label(&self, id: Entity) -> Option<&str> {
+        self.entities
+            .iter()
+            .find(|(_, other)| *other == id)
+            .map(|(label, _)| label.as_str())
+    }
+
+    fn matches<'a>(&'a self, input: &'a str) -> impl Iterator<Item = &'a (String, Entity)> {
+        self.entities
+            .iter()
+            .filter(move |(label, _)| label.starts_with(input))
+    }
+}
+
+impl Lookup for EntityLookup {
+    fn lookup(&self, input: &str) -> Result<(), LookupError> {
+        if self.entities.iter().any(|(label, _)| label == input) {
+            Ok(())
+        } else {
+            Err(iter_to_error(self.matches(input).map(|(label, _)| label)))
+        }
+    }
+
+    fn longest_prefix(&self, input: &str) -> Option<String> {
+        let labels: Vec<&str> = self
+            .matches(input)
+            .map(|(label, _)| label.as_str())
+            .collect();
+        labels.as_slice().longest_prefix(input)
+    }
+
+    fn all_lookups(&self, input: &str) -> Vec<String> {
+        self.matches(input)
+            .map(|(label, _)| label.clone())
+            .collect()
+    }
+}
+
+impl LookupMap for EntityLookup {
+    type Item = Entity;
+
+    fn resolve(&self, input: &str) -> Option<Self::Item> {
+        if let Some((_, id)) = self.entities.iter().find(|(label, _)| label == input) {
+            return Some(*id);
+        }
+        let mut matches = self.matches(input);
+        match (matches.next(), matches.next()) {
+            (Some((_, id)), None) => Some(*id),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn entity_lookup() {
+        let mut world = World::new();
+        let hero = world.spawn(Name::new("hero")).id();
+        let goblin_a = world.spawn(Name::new("goblin")).id();
+        let goblin_b = world.spawn(Name::new("goblin")).id();
+        world.spawn_empty();
+        let mut query = world.query::<(Entity, &Name)>();
+        let lookup = EntityLookup::new(query.iter(&world));
+        let a = format!("goblin#{}", goblin_a.index());
+        let b = format!("goblin#{}", goblin_b.index());
+        assert_eq!(
+            lookup.all_lookups(""),
+            vec![a.clone(), b.clone(), "hero".into()]
+        );
+        assert_eq!(lookup.label(hero), Some("hero"));
+        assert!(matches!(
+            lookup.lookup("gob"),
+            Err(LookupError::ManyMatches)
+        ));
+        assert_eq!(lookup.longest_prefix("g"), Some("goblin#".into()));
+        assert!(lookup.lookup(&a).is_ok());
+        assert_eq!(lookup.resolve(&a), Some(goblin_a));
+        assert_eq!(lookup.resolve(&b), Some(goblin_b));
+        assert_eq!(lookup.resolve("he"), Some(hero));
+        assert_eq!(lookup.resolve("goblin"), None);
+    }
+}
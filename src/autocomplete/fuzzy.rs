@@ -0,0 +1,241 @@
+//! Fuzzy matching
+use super::{lookup::iter_to_error, Lookup, LookupError, LookupMap};
+
+/// Score of each matched character
+const MATCH: i32 = 16;
+/// Bonus for a match that follows the previous match
+const CONSECUTIVE: i32 = 16;
+/// Bonus for a match at the start of a word
+const BOUNDARY: i32 = 24;
+/// Most that leading unmatched characters can cost
+const MAX_LEADING: i32 = 8;
+
+/// Match `pattern` as a case-insensitive subsequence of `candidate`.
+///
+/// Returns a score, higher is better, and the char indices of `candidate` that
+/// matched. Matches at word boundaries and consecutive matches score higher;
+/// gaps cost a little.
+///
+/// ```
+/// use bevy_minibuffer::autocomplete::fuzzy_match;
+/// let (_score, indices) = fuzzy_match("spen", "debug_spawn_enemy").unwrap();
+/// assert_eq!(indices, vec![6, 7, 12, 13]);
+/// assert!(fuzzy_match("xyz", "debug_spawn_enemy").is_none());
+/// ```
+pub fn fuzzy_match(pattern: &str, candidate: &str) -> Option<(i32, Vec<usize>)> {
+    let pattern: Vec<char> = pattern.chars().flat_map(char::to_lowercase).collect();
+    if pattern.is_empty() {
+        return Some((0, Vec::new()));
+    }
+    let chars: Vec<char> = candidate.chars().collect();
+    let eq = |c: char, p: char| c.to_lowercase().eq(std::iter::once(p));
+
+    // Find where the leftmost match ends.
+    let mut p = 0;
+    let mut end = None;
+    for (i, c) in chars.iter().enumerate() {
+        if eq(*c, pattern[p]) {
+            p += 1;
+            if p == pattern.len() {
+                end = Some(i);
+                break;
+            }
+        }
+    }
+    let end = end?;
+
+    // Walk backwards from there to find the tightest start.
+    let mut p = pattern.len();
+    let mut start = end;
+    for i in (0..=end).rev() {
+        if eq(chars[i], pattern[p - 1]) {
+            p -= 1;
+            if p == 0 {
+                start = i;
+                break;
+            }
+        }
+    }
+
+    let mut indices = Vec::with_capacity(pattern.len());
+    let mut p = 0;
+    for (i, c) in chars.iter().enumerate().take(end + 1).skip(start) {
+        if p < pattern.len() && eq(*c, pattern[p]) {
+            indices.push(i);
+            p += 1;
+        }
+    }
+
+    let mut score = -(start as i32).min(MAX_LEADING);
+    let mut last: Option<usize> = None;
+    for &i in &indices {
+        score += MATCH;
+        if let Some(last) = last {
+            if i == last + 1 {
+                score += CONSECUTIVE;
+            } else {
+                score -= (i - last - 1) as i32;
+            }
+        }
+        let boundary = i == 0
+            || matches!(chars[i - 1], '_' | '-' | ' ' | '/' | ':' | '.')
+            || (chars[i - 1].is_lowercase() && chars[i].is_uppercase());
+        if boundary {
+            score += BOUNDARY;
+        }
+        last = Some(i);
+    }
+    Some((score, indices))
+}
+
+/// Wrap a [Lookup] to match its entries fuzzily rather than by prefix.
+///
+/// Typing "spawn" will find "debug_spawn_enemy". Results of
+/// [Lookup::all_lookups] are ranked by their [fuzzy_match] score, and the
+/// matched characters are highlighted in the completion panel.
+///
+/// ```
+/// use bevy_minibuffer::autocomplete::{FuzzyLookup, Lookup};
+/// let lookup = FuzzyLookup::new(vec!["debug_spawn_enemy", "spawn", "despawn_all"]);
+/// assert_eq!(lookup.all_lookups("spawn"), vec!["spawn", "debug_spawn_enemy", "despawn_all"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct FuzzyLookup<L> {
+    lookup: L,
+}
+
+impl<L> FuzzyLookup<L> {
+    /// Wrap a lookup.
+    pub fn new(lookup: L) -> Self {
+        Self { lookup }
+    }
+
+    /// Return the wrapped lookup.
+    pub fn into_inner(self) -> L {
+        self.lookup
+    }
+}
+
+impl<L: Lookup> Lookup for FuzzyLookup<L> {
+    fn lookup(&self, input: &str) -> Result<(), LookupError> {
+        if self.lookup.lookup(input).is_ok() {
+            return Ok(());
+        }
+        Err(iter_to_error(self.all_lookups(input).into_iter()))
+    }
+
+    fn longest_prefix(&self, input: &str) -> Option<String> {
+        let matches = self.all_lookups(input);
+        let (first, rest) = matches.split_first()?;
+        let mut prefix: &str = first;
+        for word in rest {
+            let len = prefix
+                .char_indices()
+                .zip(word.chars())
+                .find(|((_, a), b)| a != b)
+                .map(|((i, _), _)| i)
+                .unwrap_or(prefix.len().min(word.len()));
+            prefix = &prefix[..len];
+        }
+        // Only replace the input if the prefix still matches it, otherwise the
+        // user would lose what they typed.
+        if prefix.chars().count() > input.chars().count() && fuzzy_match(input, prefix).is_some() {
+            Some(prefix.to_string())
+        } else {
+            Some(input.to_string())
+        }
+    }
+
+    fn all_lookups(&self, input: &str) -> Vec<String> {
+        let mut matches: Vec<(i32, String)> = self
+            .lookup
+            .all_lookups("")
+            .into_iter()
+            .filter_map(|word| fuzzy_match(input, &word).map(|(score, _)| (score, word)))
+            .collect();
+        matches.sort_by(|(a_score, a), (b_score, b)| {
+            b_score
+                .cmp(a_score)
+                .then_with(|| a.len().cmp(&b.len()))
+                .then_with(|| a.cmp(b))
+        });
+        matches.into_iter().map(|(_, word)| word).collect()
+    }
+
+    fn highlights(&self, input: &str, candidate: &str) -> Vec<usize> {
+        fuzzy_match(input, candidate)
+            .map(|(_, indices)| indices)
+            .unwrap_or_default()
+    }
+}
+
+impl<L: LookupMap> LookupMap for FuzzyLookup<L> {
+    type Item = L::Item;
+
+    fn resolve(&self, input: &str) -> Option<Self::Item> {
+        if self.lookup.lookup(input).is_ok() {
+            return self.lookup.resolve(input);
+        }
+        // Resolve only if there is one fuzzy match, not one prefix match.
+        match &self.all_lookups(input)[..] {
+            [one] => self.lookup.resolve(one),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn match_indices() {
+        assert_eq!(fuzzy_match("", "abc"), Some((0, vec![])));
+        assert_eq!(fuzzy_match("ac", "abc").map(|x| x.1), Some(vec![0, 2]));
+        assert_eq!(fuzzy_match("AC", "abc").map(|x| x.1), Some(vec![0, 2]));
+        assert_eq!(fuzzy_match("ca", "abc"), None);
+        // Prefer the tightest match.
+        assert_eq!(fuzzy_match("ab", "a_xab").map(|x| x.1), Some(vec![3, 4]));
+    }
+
+    #[test]
+    fn match_ranking() {
+        let (prefix, _) = fuzzy_match("spawn", "spawn_enemy").unwrap();
+        let (word, _) = fuzzy_match("spawn", "debug_spawn_enemy").unwrap();
+        let (inner, _) = fuzzy_match("spawn", "despawn").unwrap();
+        let (scattered, _) = fuzzy_match("spawn", "sxpxaxwxn").unwrap();
+        assert!(prefix > word);
+        assert!(word > inner);
+        assert!(inner > scattered);
+    }
+
+    #[test]
+    fn fuzzy_lookup() {
+        let lookup = FuzzyLookup::new(vec!["debug_spawn_enemy", "list_acts", "spawn_tree"]);
+        assert!(lookup.lookup("list_acts").is_ok());
+        assert!(matches!(
+            lookup.lookup("dse"),
+            Err(LookupError::OneMatch(s)) if s == "debug_spawn_enemy"
+        ));
+        assert!(matches!(
+            lookup.lookup("spawn"),
+            Err(LookupError::ManyMatches)
+        ));
+        assert!(matches!(lookup.lookup("zzz"), Err(LookupError::NoMatch)));
+        assert_eq!(
+            lookup.all_lookups("spawn"),
+            vec!["spawn_tree", "debug_spawn_enemy"]
+        );
+        assert_eq!(lookup.resolve("lsa"), Some("list_acts".into()));
+        assert_eq!(lookup.resolve("spawn"), None);
+    }
+
+    #[test]
+    fn fuzzy_longest_prefix() {
+        let lookup = FuzzyLookup::new(vec!["debug_spawn_enemy", "debug_spawn_tree"]);
+        assert_eq!(lookup.longest_prefix("spawn"), Some("debug_spawn_".into()));
+        let lookup = FuzzyLookup::new(vec!["a_x_enemy", "a_y_enemy"]);
+        assert_eq!(lookup.longest_prefix("enemy"), Some("enemy".into()));
+        assert_eq!(lookup.longest_prefix("zzz"), None);
+    }
+}
@@ -39,6 +39,11 @@ pub trait Lookup {
     fn longest_prefix(&self, input: &str) -> Option<String>;
     /// Return all matches for `input`.
     fn all_lookups(&self, input: &str) -> Vec<String>;
+    /// Return the char indices of `candidate` that matched `input`. These are
+    /// highlighted in the completion panel. None by default.
+    fn highlights(&self, _input: &str, _candidate: &str) -> Vec<usize> {
+        Vec::new()
+    }
 }
 
 /// LookupMap the input to a value of type `Item`.
@@ -135,7 +140,7 @@ impl<V: Send + Sync + Clone> LookupMap for map::Trie<u8, V> {
     }
 }
 
-fn iter_to_error(mut matches: impl Iterator<Item = impl AsRef<str>>) -> LookupError {
+pub(super) fn iter_to_error(mut matches: impl Iterator<Item = impl AsRef<str>>) -> LookupError {
     if let Some(one_match) = matches.next() {
         if matches.next().is_none() {
             LookupError::OneMatch(one_match.as_ref().to_string())
@@ -1,5 +1,11 @@
 //! Tab completion functionality
-use crate::{event::LookupEvent, prelude::*};
+use crate::{
+    event::{Completion, LookupEvent},
+    history::is_alt_history_key,
+    prelude::*,
+    prompt::CompletionState,
+    ui::ScrollingList,
+};
 use bevy::{
     diagnostic::FrameCount,
     ecs::system::EntityCommands,
@@ -16,8 +22,22 @@ use bevy_asky::{
     AskySet, Submitter,
 };
 use std::borrow::Cow;
+mod case_insensitive;
+mod categorized;
+mod command_line;
+mod entity;
+mod fuzzy;
 mod lookup;
+mod path;
+mod variant;
+pub use case_insensitive::*;
+pub use categorized::*;
+pub use command_line::*;
+pub use entity::*;
+pub use fuzzy::*;
 pub use lookup::*;
+pub use path::*;
+pub use variant::*;
 
 /// Prompt to get one-line user input.
 ///
@@ -30,6 +50,14 @@ pub use lookup::*;
 /// | `Delete`    | Delete current character     |
 /// | `Left`      | Move cursor left             |
 /// | `Right`     | Move cursor right            |
+/// | `Tab`       | Complete                     |
+/// | `Up`, `Alt-P` | Previous history entry     |
+/// | `Down`, `Alt-N` | Next history entry       |
+/// | `Down`, `Ctrl-N` | Select next completion  |
+/// | `Up`, `Ctrl-P` | Select previous completion |
+///
+/// While the completion panel is shown, `Up` and `Down` move the selection
+/// instead of recalling history, and `Enter` accepts the selected completion.
 ///
 #[derive(Component, Deref)]
 // #[reflect(opaque)]
@@ -104,6 +132,21 @@ unsafe impl Submitter for AutoComplete {
 //     }
 // }
 
+/// Collect the completions for `input` with their highlights.
+fn completions(lookup: &AutoComplete, input: &str) -> LookupEvent {
+    LookupEvent::Completions(
+        lookup
+            .all_lookups(input)
+            .into_iter()
+            .map(|label| Completion {
+                highlights: lookup.highlights(input, &label),
+                label,
+            })
+            .collect(),
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
 fn autocomplete_controller(
     mut focus: FocusParam,
     mut query: Query<(
@@ -116,6 +159,9 @@ fn autocomplete_controller(
     mut commands: Commands,
     mut lookup_events: EventWriter<LookupEvent>,
     frame_count: Res<FrameCount>,
+    keys: Res<ButtonInput<KeyCode>>,
+    mut lists: Query<&mut ScrollingList>,
+    completion_state: Res<State<CompletionState>>,
 ) {
     let mut any_focused_text = false;
     for (id, mut text_state, autocomplete, require_match) in query.iter_mut() {
@@ -128,6 +174,33 @@ fn autocomplete_controller(
                 continue;
             }
             trace!("input {:?} frame {}", &ev.logical_key, frame_count.0);
+            let ctrl = keys.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+            let next = ev.logical_key == Key::ArrowDown || (ctrl && ev.key_code == KeyCode::KeyN);
+            let previous = ev.logical_key == Key::ArrowUp || (ctrl && ev.key_code == KeyCode::KeyP);
+            if next || previous {
+                // Arrows recall history when the completion panel is hidden.
+                if *completion_state.get() == CompletionState::Visible {
+                    if let Ok(mut list) = lists.single_mut() {
+                        if next {
+                            list.select_next();
+                        } else {
+                            list.select_previous();
+                        }
+                    }
+                }
+                continue;
+            }
+            if matches!(
+                ev.logical_key,
+                Key::Character(_) | Key::Space | Key::Backspace | Key::Delete
+            ) {
+                // The completions shown no longer match the input.
+                if let Ok(mut list) = lists.single_mut() {
+                    if list.selection.is_some() {
+                        list.selection = None;
+                    }
+                }
+            }
             match &ev.logical_key {
                 Key::Tab => {
                     if let Err(e) = autocomplete.lookup(&text_state.value) {
@@ -135,7 +208,7 @@ fn autocomplete_controller(
                         match e {
                             NoMatch => {
                                 lookup_events.write(LookupEvent::Hide);
-                                if let Some(mut ecommands) = commands.get_entity(id).ok() {
+                                if let Ok(mut ecommands) = commands.get_entity(id) {
                                     ecommands.try_insert(Feedback::info(format!("{}", e)));
                                 }
                             }
@@ -144,9 +217,7 @@ fn autocomplete_controller(
                                 text_state.set_value(&s);
                             }
                             ManyMatches => {
-                                let matches = autocomplete.all_lookups(&text_state.value);
-
-                                lookup_events.write(LookupEvent::Completions(matches));
+                                lookup_events.write(completions(autocomplete, &text_state.value));
                                 if let Some(new_input) =
                                     autocomplete.longest_prefix(&text_state.value)
                                 {
@@ -155,13 +226,15 @@ fn autocomplete_controller(
                             }
                             Message(s) => {
                                 lookup_events.write(LookupEvent::Hide);
-                                if let Some(mut ecommands) = commands.get_entity(id).ok() {
+                                if let Ok(mut ecommands) = commands.get_entity(id) {
                                     ecommands.try_insert(Feedback::info(s)); // Err(s),
                                 }
                             }
                         }
                     }
                 }
+                // Alt-P and Alt-N recall history.
+                Key::Character(_) if is_alt_history_key(ev, &keys) => {}
                 Key::Character(s) => {
                     for c in s.chars() {
                         text_state.insert(c);
@@ -173,19 +246,28 @@ fn autocomplete_controller(
                 Key::ArrowLeft => text_state.move_cursor(CursorDirection::Left),
                 Key::ArrowRight => text_state.move_cursor(CursorDirection::Right),
                 Key::Enter => {
+                    if *completion_state.get() == CompletionState::Visible {
+                        if let Some(selected) = lists
+                            .single()
+                            .ok()
+                            .and_then(|list| list.selected().map(String::from))
+                        {
+                            text_state.set_value(&selected);
+                        }
+                    }
                     if require_match.is_some() {
                         if let Err(e) = autocomplete.lookup(&text_state.value) {
                             use LookupError::*;
                             match e {
                                 NoMatch => {
                                     lookup_events.write(LookupEvent::Hide);
-                                    if let Some(mut ecommands) = commands.get_entity(id).ok() {
+                                    if let Ok(mut ecommands) = commands.get_entity(id) {
                                         ecommands.try_insert(Feedback::info(format!("{}", e)));
                                     }
                                 }
                                 Message(s) => {
                                     lookup_events.write(LookupEvent::Hide);
-                                    if let Some(mut ecommands) = commands.get_entity(id).ok() {
+                                    if let Ok(mut ecommands) = commands.get_entity(id) {
                                         ecommands.try_insert(Feedback::info(s));
                                     }
                                 }
@@ -194,12 +276,11 @@ fn autocomplete_controller(
                                     text_state.set_value(&s);
                                 }
                                 ManyMatches => {
-                                    if let Some(mut ecommands) = commands.get_entity(id).ok() {
+                                    if let Ok(mut ecommands) = commands.get_entity(id) {
                                         ecommands.try_insert(Feedback::warn("require match"));
                                     }
-                                    let matches = autocomplete.all_lookups(&text_state.value);
-
-                                    lookup_events.write(LookupEvent::Completions(matches));
+                                    lookup_events
+                                        .write(completions(autocomplete, &text_state.value));
                                     if let Some(new_input) =
                                         autocomplete.longest_prefix(&text_state.value)
                                     {
@@ -217,7 +298,7 @@ fn autocomplete_controller(
                 Key::Escape => {
                     commands
                         .trigger_targets(Submit::<String>::new(Err(bevy_asky::Error::Cancel)), id);
-                    if let Some(mut ecommands) = commands.get_entity(id).ok() {
+                    if let Ok(mut ecommands) = commands.get_entity(id) {
                         ecommands.try_insert(Feedback::error("canceled"));
                     }
                     focus.block(id);
@@ -0,0 +1,204 @@
+//! Complete file paths
+use super::{lookup::iter_to_error, Lookup, LookupError, LookupMap};
+use std::{
+    borrow::Cow,
+    path::{Component, Path, PathBuf},
+};
+
+/// Look up paths relative to a root directory, e.g., "assets".
+///
+/// It completes one path component at a time. Directories end with `/`. Only
+/// files with the given extensions are offered if any are given. It resolves
+/// to the path joined to the root. Paths that leave the root, like "../x" or
+/// "/x", are never matched.
+///
+/// ```no_run
+/// use bevy::prelude::*;
+/// use bevy_minibuffer::prelude::*;
+/// use std::path::PathBuf;
+///
+/// fn load_scene(mut minibuffer: Minibuffer) {
+///     let lookup = PathLookup::new("assets").extension("scn.ron");
+///     minibuffer.prompt_map("Load scene: ", lookup).observe(
+///         |mut trigger: Trigger<Completed<PathBuf>>, mut minibuffer: Minibuffer| {
+///             if let Some(Ok(path)) = trigger.event_mut().take_result() {
+///                 minibuffer.message(format!("Loading {}.", path.display()));
+///             }
+///         },
+///     );
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PathLookup {
+    root: PathBuf,
+    extensions: Vec<Cow<'static, str>>,
+    allow_new: bool,
+}
+
+impl PathLookup {
+    /// Look up paths in `root`.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            extensions: Vec::new(),
+            allow_new: false,
+        }
+    }
+
+    /// Only offer files with this extension, e.g., "ron". May be given more
+    /// than once.
+    pub fn extension(mut self, extension: impl Into<Cow<'static, str>>) -> Self {
+        let extension = extension.into();
+        self.extensions.push(match extension.strip_prefix('.') {
+            Some(stripped) => stripped.to_string().into(),
+            None => extension,
+        });
+        self
+    }
+
+    /// Accept files that do not exist yet in an existing directory, e.g., to
+    /// save a file.
+    pub fn allow_new(mut self) -> Self {
+        self.allow_new = true;
+        self
+    }
+
+    /// Return the root directory.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Return true if `input` stays within the root.
+    fn is_relative(input: &str) -> bool {
+        Path::new(input)
+            .components()
+            .all(|component| matches!(component, Component::Normal(_) | Component::CurDir))
+    }
+
+    fn has_extension(&self, name: &str) -> bool {
+        self.extensions.is_empty()
+            || self.extensions.iter().any(|extension| {
+                name.len() > extension.len() + 1
+                    && name
+                        .to_lowercase()
+                        .ends_with(&format!(".{}", extension.to_lowercase()))
+            })
+    }
+
+    /// Return the entries that complete the last component of `input`.
+    fn candidates(&self, input: &str) -> Vec<String> {
+        if !Self::is_relative(input) {
+            return Vec::new();
+        }
+        let (dir, partial) = match input.rfind('/') {
+            Some(i) => input.split_at(i + 1),
+            None => ("", input),
+        };
+        let Ok(entries) = std::fs::read_dir(self.root.join(dir)) else {
+            return Vec::new();
+        };
+        let mut candidates: Vec<String> = entries
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let name = entry.file_name().into_string().ok()?;
+                if !name.starts_with(partial)
+                    || (name.starts_with('.') && !partial.starts_with('.'))
+                {
+                    return None;
+                }
+                if entry.path().is_dir() {
+                    Some(format!("{dir}{name}/"))
+                } else if self.has_extension(&name) {
+                    Some(format!("{dir}{name}"))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        candidates.sort();
+        candidates
+    }
+
+    /// Return true if `input` is an acceptable file.
+    fn accepts(&self, input: &str) -> bool {
+        if input.is_empty() || input.ends_with('/') || !Self::is_relative(input) {
+            return false;
+        }
+        let path = self.root.join(input);
+        let name = input.rsplit('/').next().unwrap_or(input);
+        if !self.has_extension(name) {
+            return false;
+        }
+        if path.is_file() {
+            return true;
+        }
+        self.allow_new && !path.exists() && path.parent().is_some_and(|parent| parent.is_dir())
+    }
+}
+
+impl Lookup for PathLookup {
+    fn lookup(&self, input: &str) -> Result<(), LookupError> {
+        if self.accepts(input) {
+            Ok(())
+        } else {
+            Err(iter_to_error(self.candidates(input).into_iter()))
+        }
+    }
+
+    fn longest_prefix(&self, input: &str) -> Option<String> {
+        self.candidates(input).longest_prefix(input)
+    }
+
+    fn all_lookups(&self, input: &str) -> Vec<String> {
+        self.candidates(input)
+    }
+}
+
+impl LookupMap for PathLookup {
+    type Item = PathBuf;
+
+    fn resolve(&self, input: &str) -> Option<Self::Item> {
+        self.accepts(input).then(|| self.root.join(input))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn path_lookup() {
+        let root = std::env::temp_dir().join(format!("minibuffer_paths_{}", std::process::id()));
+        fs::create_dir_all(root.join("scenes/levels")).unwrap();
+        for file in ["tapes.ron", "notes.txt", ".hidden.ron", "scenes/level1.ron"] {
+            fs::write(root.join(file), "").unwrap();
+        }
+        let lookup = PathLookup::new(&root).extension(".ron");
+        assert_eq!(lookup.all_lookups(""), vec!["scenes/", "tapes.ron"]);
+        assert!(matches!(lookup.lookup("sc"), Err(LookupError::OneMatch(s)) if s == "scenes/"));
+        assert_eq!(
+            lookup.all_lookups("scenes/"),
+            vec!["scenes/level1.ron", "scenes/levels/"]
+        );
+        assert_eq!(
+            lookup.longest_prefix("scenes/l"),
+            Some("scenes/level".into())
+        );
+        assert!(lookup.lookup("scenes/level1.ron").is_ok());
+        assert_eq!(
+            lookup.resolve("scenes/level1.ron"),
+            Some(root.join("scenes/level1.ron"))
+        );
+        assert_eq!(lookup.resolve("notes.txt"), None);
+        assert_eq!(lookup.resolve("scenes/"), None);
+        assert_eq!(lookup.resolve("new.ron"), None);
+        assert_eq!(lookup.all_lookups("../"), Vec::<String>::new());
+
+        let lookup = lookup.allow_new();
+        assert_eq!(lookup.resolve("new.ron"), Some(root.join("new.ron")));
+        assert_eq!(lookup.resolve("new.txt"), None);
+        assert_eq!(lookup.resolve("missing/new.ron"), None);
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
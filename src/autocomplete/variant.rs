@@ -0,0 +1,126 @@
+//! Pick a variant of a reflected enum
+use super::{Lookup, LookupError, LookupMap};
+use bevy::reflect::{DynamicEnum, DynamicVariant, FromReflect, TypeInfo, Typed, VariantInfo};
+use std::{fmt, marker::PhantomData};
+
+/// Look up the unit variants of a [Reflect] enum by name and construct them
+/// with [FromReflect].
+///
+/// Variants with fields are not offered. A type that is not an enum has no
+/// variants.
+///
+/// ```
+/// use bevy::prelude::*;
+/// use bevy_minibuffer::prelude::*;
+/// #[derive(Debug, Reflect, PartialEq)]
+/// enum Difficulty {
+///     Easy,
+///     Normal,
+///     Hard,
+/// }
+///
+/// let lookup = VariantLookup::<Difficulty>::new();
+/// assert_eq!(lookup.all_lookups(""), ["Easy", "Hard", "Normal"]);
+/// assert_eq!(lookup.resolve("Hard"), Some(Difficulty::Hard));
+/// ```
+///
+/// [Reflect]: bevy::reflect::Reflect
+pub struct VariantLookup<T> {
+    /// Variant names sorted
+    variants: Vec<&'static str>,
+    _type: PhantomData<fn() -> T>,
+}
+
+impl<T: Typed> VariantLookup<T> {
+    /// Create a lookup of `T`'s unit variants.
+    pub fn new() -> Self {
+        let mut variants: Vec<&'static str> = match T::type_info() {
+            TypeInfo::Enum(info) => info
+                .iter()
+                .filter_map(|variant| match variant {
+                    VariantInfo::Unit(unit) => Some(unit.name()),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        variants.sort();
+        Self {
+            variants,
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<T: Typed> Default for VariantLookup<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for VariantLookup<T> {
+    fn clone(&self) -> Self {
+        Self {
+            variants: self.variants.clone(),
+            _type: PhantomData,
+        }
+    }
+}
+
+impl<T> fmt::Debug for VariantLookup<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("VariantLookup")
+            .field("variants", &self.variants)
+            .finish()
+    }
+}
+
+impl<T> Lookup for VariantLookup<T> {
+    fn lookup(&self, input: &str) -> Result<(), LookupError> {
+        self.variants.lookup(input)
+    }
+
+    fn longest_prefix(&self, input: &str) -> Option<String> {
+        self.variants.longest_prefix(input)
+    }
+
+    fn all_lookups(&self, input: &str) -> Vec<String> {
+        self.variants.all_lookups(input)
+    }
+}
+
+impl<T: FromReflect + Send + Sync> LookupMap for VariantLookup<T> {
+    type Item = T;
+
+    fn resolve(&self, input: &str) -> Option<Self::Item> {
+        let name = self.variants.iter().find(|name| **name == input)?;
+        T::from_reflect(&DynamicEnum::new(*name, DynamicVariant::Unit))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use bevy::reflect::Reflect;
+
+    #[derive(Debug, Reflect, PartialEq)]
+    enum Shape {
+        Circle,
+        Square,
+        Polygon(u8),
+    }
+
+    #[test]
+    fn variant_lookup() {
+        let lookup = VariantLookup::<Shape>::new();
+        assert_eq!(lookup.all_lookups(""), ["Circle", "Square"]);
+        assert!(matches!(
+            lookup.lookup("S"),
+            Err(LookupError::OneMatch(s)) if s == "Square"
+        ));
+        assert_eq!(lookup.resolve("Circle"), Some(Shape::Circle));
+        assert_eq!(lookup.resolve("Polygon"), None);
+        assert_eq!(lookup.resolve("Circ"), None);
+        assert!(VariantLookup::<u8>::new().all_lookups("").is_empty());
+    }
+}
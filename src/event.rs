@@ -1,6 +1,9 @@
 //! Events
 use crate::{
-    acts::{Act, ActFlags, ActRef, ActSystem, RunActMap},
+    acts::{
+        split_args, Act, ActFlags, ActRef, ActSystem, ArgParserMap, DeprecationHints, Input,
+        RunActMap,
+    },
     input::{Hotkey, KeyChord},
     prompt::PromptState,
     ui::MinibufferNode,
@@ -19,7 +22,7 @@ use bevy::{
 use bevy_channel_trigger::ChannelTriggerApp;
 // #[cfg(feature = "async")]
 // use bevy_crossbeam_event::CrossbeamEventApp;
-use std::{borrow::Cow, fmt::Debug};
+use std::{borrow::Cow, fmt::Debug, sync::Arc};
 
 pub(crate) fn plugin(app: &mut App) {
     // #[cfg(feature = "async")]
@@ -54,12 +57,14 @@ fn setup_observers(query: Query<Entity, With<MinibufferNode>>, mut commands: Com
 }
 
 /// Requests an act to be run
-#[derive(Clone, Event, Debug, Copy)]
+#[derive(Clone, Event, Debug)]
 pub struct RunActEvent {
     /// The act to run
     pub(crate) act: ActRef,
     /// Which if any of its hotkeys started it
     pub hotkey: Option<usize>,
+    /// Input for an act that accepts one; otherwise it asks for its input
+    pub(crate) input: Option<Input>,
 }
 
 /// Requests an act by name to be run
@@ -67,6 +72,8 @@ pub struct RunActEvent {
 pub struct RunActByNameEvent {
     /// Name of the act to run
     pub name: Cow<'static, str>,
+    /// Inline arguments parsed into the act's input by [ArgParserMap]
+    pub args: Vec<String>,
 }
 
 impl RunActByNameEvent {
@@ -74,9 +81,25 @@ impl RunActByNameEvent {
     pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
         Self {
             name: name.into(),
-            // input: None,
+            args: Vec::new(),
         }
     }
+
+    /// Add inline arguments.
+    pub fn with_args(mut self, args: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Parse a line like `spawn_enemy 5 "goblin"` into an act name and its
+    /// arguments. See [split_args].
+    pub fn parse(line: &str) -> Result<Self, Error> {
+        let mut args = split_args(line)?.into_iter();
+        let name = args
+            .next()
+            .ok_or_else(|| Error::Message("No act name given.".into()))?;
+        Ok(Self::new(name).with_args(args))
+    }
 }
 
 /// This holds the last act run.
@@ -87,6 +110,11 @@ impl RunActByNameEvent {
 pub struct LastRunAct(Option<RunActEvent>);
 
 impl LastRunAct {
+    /// Return the last run act event.
+    pub fn event(&self) -> Option<&RunActEvent> {
+        self.0.as_ref()
+    }
+
     /// Return the hotkey associated with this run.
     pub fn hotkey(&self, acts: &mut QueryLens<&Act>) -> Option<Hotkey> {
         self.0.as_ref().and_then(|run_act| run_act.hotkey(acts))
@@ -108,7 +136,7 @@ impl RunActEvent {
         Self {
             act,
             hotkey: None,
-            // input: None,
+            input: None,
         }
     }
 
@@ -119,10 +147,21 @@ impl RunActEvent {
                 flags: act.flags,
             },
             hotkey: None,
-            // input: None,
+            input: None,
         }
     }
 
+    /// Run the act with this input instead of asking for it.
+    pub fn with_input<I: Send + Sync + 'static>(mut self, input: I) -> Self {
+        self.input = Some(Arc::new(input));
+        self
+    }
+
+    /// Return the input if given.
+    pub fn input(&self) -> Option<&Input> {
+        self.input.as_ref()
+    }
+
     /// Set the hotkey index.
     pub fn with_hotkey(mut self, index: usize) -> Self {
         self.hotkey = Some(index);
@@ -131,10 +170,10 @@ impl RunActEvent {
 
     /// Return the hotkey associated with this run.
     pub fn hotkey(&self, acts: &mut QueryLens<&Act>) -> Option<Hotkey> {
-        acts.query()
-            .get(self.act.id)
-            .ok()
-            .and_then(|act| self.hotkey.map(|index| act.hotkeys[index].clone()))
+        acts.query().get(self.act.id).ok().and_then(|act| {
+            self.hotkey
+                .and_then(|index| act.hotkeys.get(index).cloned())
+        })
     }
 }
 
@@ -165,7 +204,16 @@ pub(crate) enum LookupEvent {
     /// Hide the autocomplete panel
     Hide,
     /// Show completions
-    Completions(Vec<String>),
+    Completions(Vec<Completion>),
+}
+
+/// A completion candidate
+#[derive(Debug, Clone, Hash)]
+pub(crate) struct Completion {
+    /// The candidate
+    pub label: String,
+    /// Char indices of `label` to highlight
+    pub highlights: Vec<usize>,
 }
 
 /// Dispatch an event
@@ -216,10 +264,10 @@ pub(crate) fn dispatch_events(
                 lookup_events.write(l.clone());
             }
             RunActEvent(e) => {
-                minibuffer.run_act(e.act);
+                minibuffer.commands.trigger(e.clone());
             }
             RunActByNameEvent(e) => {
-                minibuffer.run_act(e.clone().name);
+                minibuffer.commands.trigger(e.clone());
             }
             EmitMessage(s) => {
                 minibuffer.message(s.to_string());
@@ -247,10 +295,10 @@ fn dispatch_trigger(
             lookup_events.write(l);
         }
         RunActEvent(e) => {
-            minibuffer.run_act(e.act);
+            minibuffer.commands.trigger(e);
         }
         RunActByNameEvent(e) => {
-            minibuffer.run_act(e.name);
+            minibuffer.commands.trigger(e);
         }
         EmitMessage(s) => {
             minibuffer.message(s);
@@ -289,7 +337,7 @@ impl KeyChordEvent {
 /// Run act for any [RunActEvent].
 pub(crate) fn run_acts(mut events: EventReader<RunActEvent>, mut commands: Commands) {
     for e in events.read() {
-        commands.trigger(*e);
+        commands.trigger(e.clone());
     }
 }
 
@@ -317,8 +365,12 @@ fn run_acts_obs(
         .and_then(|x| run_act_map.get(x).map(|y| &**y));
 
     let run_act = run_act.unwrap_or(&ActSystem);
-    last.0 = Some(*trigger.event());
-    if let Err(error) = run_act.run(act.system_id, &mut commands) {
+    last.0 = Some(e.clone());
+    let result = match e.input {
+        Some(ref input) => run_act.run_with_input(act.system_id, &**input, &mut commands),
+        None => run_act.run(act.system_id, &mut commands),
+    };
+    if let Err(error) = result {
         warn!("Error running act '{}': {:?}", act.name, error);
     }
 }
@@ -334,25 +386,69 @@ fn run_acts_by_name_obs(
     trigger: Trigger<RunActByNameEvent>,
     mut commands: Commands,
     acts: Query<(Entity, &Act)>,
+    arg_parsers: Res<ArgParserMap>,
+    mut hints: ResMut<DeprecationHints>,
 ) {
     let e = trigger.event();
-    if let Some((id, act)) = acts.iter().find(|(_, a)| a.name == e.name) {
-        let new_event = RunActEvent {
-            act: ActRef::from_act(act, id),
-            hotkey: None,
-        };
-        commands.trigger(new_event);
-    } else {
+    let Some((id, act)) = Act::find_by_name(acts.iter(), &e.name) else {
         warn!("No act named '{}' found.", e.name);
+        return;
+    };
+    if let Some(hint) = hints.hint(act, &e.name) {
+        warn!("{hint}");
+        commands.trigger(DispatchEvent::EmitMessage(hint));
+    }
+    let mut new_event = RunActEvent::from_act(act, id);
+    if !e.args.is_empty() {
+        match arg_parsers.parse(act, &e.args) {
+            Ok(input) => new_event.input = Some(input),
+            Err(error) => {
+                warn!("Could not run act '{}': {error}", act.name);
+                commands.trigger(DispatchEvent::EmitMessage(format!("{error}")));
+                return;
+            }
+        }
     }
+    commands.trigger(new_event);
 }
 
 #[cfg(test)]
 mod test {
-    use std::{
-        any::{Any, TypeId},
-        sync::Arc,
-    };
+    use super::*;
+    use crate::acts::ActBuilder;
+    use std::any::{Any, TypeId};
+
+    #[derive(Resource, Default)]
+    struct Spawned(Vec<(u32, String)>);
+
+    fn spawn_enemy(In(input): In<(u32, String)>, mut spawned: ResMut<Spawned>) {
+        spawned.0.push(input);
+    }
+
+    #[test]
+    fn run_with_args() {
+        let mut world = World::new();
+        world.init_resource::<RunActMap>();
+        world.init_resource::<ArgParserMap>();
+        world.init_resource::<LastRunAct>();
+        world.init_resource::<DeprecationHints>();
+        world.init_resource::<FrameCount>();
+        world.init_resource::<Spawned>();
+        world
+            .resource_mut::<ArgParserMap>()
+            .register_from_args::<(u32, String)>();
+        Command::apply(ActBuilder::new_with_input(spawn_enemy), &mut world);
+        world.add_observer(run_acts_by_name_obs);
+        world.add_observer(run_acts_obs);
+
+        world.trigger(RunActByNameEvent::parse("spawn_enemy 5 \"goblin king\"").unwrap());
+        world.trigger(RunActByNameEvent::new("spawn_enemy").with_args(["five", "goblin"]));
+        world.flush();
+        assert_eq!(
+            world.resource::<Spawned>().0,
+            vec![(5, "goblin king".to_string())]
+        );
+    }
 
     #[test]
     fn test_arc_typeid() {
@@ -3,7 +3,7 @@
 //! It uses promises rather than triggers.
 use crate::{
     acts::ActArg,
-    autocomplete::{AutoComplete, Completed, Lookup, LookupMap},
+    autocomplete::{AutoComplete, Completed, Lookup, LookupMap, VariantLookup},
     event::{DispatchEvent, KeyChordEvent, RunActByNameEvent, RunActEvent},
     prompt::{GetKeyChord, PromptState},
     ui::PromptContainer,
@@ -19,6 +19,7 @@ use bevy::{
         world::{unsafe_world_cell::UnsafeWorldCell, World},
     },
     prelude::{Bundle, Component, State, Trigger},
+    reflect::{FromReflect, Typed},
 };
 use bevy_asky::{
     construct::{Add0, Construct},
@@ -236,6 +237,19 @@ impl MinibufferAsync {
         }
     }
 
+    /// Read a unit variant of a [Reflect] enum by name.
+    ///
+    /// [Reflect]: bevy::reflect::Reflect
+    pub fn prompt_enum<T>(
+        &mut self,
+        prompt: impl Into<Cow<'static, str>>,
+    ) -> impl Future<Output = Result<T, Error>> + '_
+    where
+        T: FromReflect + Typed + Debug + Send + Sync,
+    {
+        self.prompt_map(prompt, VariantLookup::<T>::new())
+    }
+
     /// Clear the minibuffer.
     pub fn clear(&mut self) {
         self.trigger.send(DispatchEvent::Clear);
@@ -0,0 +1,275 @@
+//! Prompt history
+//!
+//! Like Emacs' `minibuffer-history`, what one submits to a prompt is
+//! remembered. While a [TextField] or autocomplete prompt is focused, `Up` or
+//! `Alt-P` recalls an older entry, and `Down` or `Alt-N` a newer one.
+//!
+//! Entries are kept per category. A prompt's category is its
+//! [HistoryCategory] if present; otherwise it is the prompt's text.
+use crate::{autocomplete::AutoComplete, event::LookupEvent, prompt::CompletionState};
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput},
+        ButtonState,
+    },
+    prelude::*,
+};
+use bevy_asky::{focus::FocusParam, prelude::*, string_cursor::StringCursor, AskySet};
+use std::{
+    borrow::Cow,
+    collections::{HashMap, VecDeque},
+};
+
+pub(crate) fn plugin(app: &mut App) {
+    app.init_resource::<MinibufferHistory>()
+        .add_observer(record_history)
+        .add_systems(
+            Update,
+            history_controller
+                .after(AskySet::Controller)
+                .before(AskySet::View),
+        );
+}
+
+/// The history of submitted prompt entries by category
+#[derive(Resource, Debug, Clone)]
+pub struct MinibufferHistory {
+    entries: HashMap<Cow<'static, str>, VecDeque<String>>,
+    /// Maximum number of entries kept per category
+    pub max_len: usize,
+}
+
+impl Default for MinibufferHistory {
+    fn default() -> Self {
+        Self {
+            entries: HashMap::default(),
+            max_len: 100,
+        }
+    }
+}
+
+impl MinibufferHistory {
+    /// Add an entry to a category's history.
+    ///
+    /// Empty entries are ignored. An earlier copy of the same entry is
+    /// removed so that it only appears as the most recent.
+    pub fn push(&mut self, category: impl Into<Cow<'static, str>>, entry: impl Into<String>) {
+        let entry = entry.into();
+        if entry.is_empty() {
+            return;
+        }
+        let entries = self.entries.entry(category.into()).or_default();
+        entries.retain(|x| *x != entry);
+        entries.push_front(entry);
+        entries.truncate(self.max_len);
+    }
+
+    /// Return a category's history, most recent first.
+    pub fn get(&self, category: &str) -> impl Iterator<Item = &str> {
+        self.entries
+            .get(category)
+            .into_iter()
+            .flat_map(|entries| entries.iter().map(|x| x.as_str()))
+    }
+
+    /// Return the `index`th most recent entry of a category.
+    pub fn nth(&self, category: &str, index: usize) -> Option<&str> {
+        self.entries
+            .get(category)
+            .and_then(|entries| entries.get(index))
+            .map(|x| x.as_str())
+    }
+
+    /// Clear a category's history.
+    pub fn clear(&mut self, category: &str) {
+        self.entries.remove(category);
+    }
+}
+
+/// Use this history category for a prompt instead of its prompt text.
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_minibuffer::{prelude::*, history::HistoryCategory};
+/// fn ask_name(mut minibuffer: Minibuffer) {
+///     minibuffer
+///         .prompt::<TextField>("Name: ")
+///         .insert(HistoryCategory::new("name"));
+/// }
+/// ```
+#[derive(Component, Debug, Clone)]
+pub struct HistoryCategory(pub Cow<'static, str>);
+
+impl HistoryCategory {
+    /// Create a history category.
+    pub fn new(category: impl Into<Cow<'static, str>>) -> Self {
+        Self(category.into())
+    }
+}
+
+/// Where a prompt is in its history
+#[derive(Component, Debug, Default)]
+struct HistoryCursor {
+    index: Option<usize>,
+    /// What was typed before recalling history
+    pending: String,
+}
+
+/// Prompts that keep a history
+type HistoryPrompt = Or<(With<TextField>, With<AutoComplete>)>;
+
+fn category<'a>(prompt: &'a Prompt, category: Option<&'a HistoryCategory>) -> &'a str {
+    category.map(|x| &*x.0).unwrap_or(&prompt.0)
+}
+
+fn record_history(
+    trigger: Trigger<Submit<String>>,
+    prompts: Query<(&Prompt, Option<&HistoryCategory>), HistoryPrompt>,
+    mut history: ResMut<MinibufferHistory>,
+) {
+    let Submit::Unhandled(Ok(entry)) = trigger.event() else {
+        return;
+    };
+    if let Ok((prompt, category_maybe)) = prompts.get(trigger.target()) {
+        history.push(category(prompt, category_maybe).to_string(), entry.clone());
+    }
+}
+
+/// Is this key event for `Alt-P` or `Alt-N`?
+pub(crate) fn is_alt_history_key(ev: &KeyboardInput, keys: &ButtonInput<KeyCode>) -> bool {
+    matches!(ev.key_code, KeyCode::KeyP | KeyCode::KeyN)
+        && keys.any_pressed([KeyCode::AltLeft, KeyCode::AltRight])
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn history_controller(
+    focus: FocusParam,
+    mut query: Query<
+        (
+            Entity,
+            &mut StringCursor,
+            &Prompt,
+            Option<&HistoryCategory>,
+            Option<&mut HistoryCursor>,
+            Has<AutoComplete>,
+        ),
+        HistoryPrompt,
+    >,
+    mut input: EventReader<KeyboardInput>,
+    keys: Res<ButtonInput<KeyCode>>,
+    completion_state: Res<State<CompletionState>>,
+    history: Res<MinibufferHistory>,
+    mut lookup_events: EventWriter<LookupEvent>,
+    mut commands: Commands,
+) {
+    let events: Vec<&KeyboardInput> = input
+        .read()
+        .filter(|ev| ev.state == ButtonState::Pressed)
+        .collect();
+    for (id, mut text_state, prompt, category_maybe, cursor_maybe, is_autocomplete) in
+        query.iter_mut()
+    {
+        if !focus.is_focused(id) {
+            continue;
+        }
+        let category = category(prompt, category_maybe);
+        let mut new_cursor = None;
+        let cursor = match cursor_maybe {
+            Some(cursor) => cursor.into_inner(),
+            None => new_cursor.insert(HistoryCursor::default()),
+        };
+        for ev in &events {
+            let alt = is_alt_history_key(ev, &keys);
+            // Arrows belong to the completion panel while it is visible.
+            let arrows = *completion_state.get() != CompletionState::Visible;
+            let older = match ev.logical_key {
+                Key::ArrowUp if arrows => true,
+                Key::ArrowDown if arrows => false,
+                _ if alt => ev.key_code == KeyCode::KeyP,
+                _ => continue,
+            };
+            if alt && !is_autocomplete {
+                // The text field has already inserted the character.
+                if let Key::Character(s) = &ev.logical_key {
+                    for _ in s.chars() {
+                        text_state.backspace();
+                    }
+                }
+            }
+            let index = if older {
+                cursor.index.map(|i| i + 1).unwrap_or(0)
+            } else {
+                match cursor.index {
+                    Some(0) => {
+                        cursor.index = None;
+                        let pending = std::mem::take(&mut cursor.pending);
+                        text_state.set_value(&pending);
+                        lookup_events.write(LookupEvent::Hide);
+                        continue;
+                    }
+                    Some(i) => i - 1,
+                    None => continue,
+                }
+            };
+            if let Some(entry) = history.nth(category, index) {
+                if cursor.index.is_none() {
+                    cursor.pending = text_state.value.clone();
+                }
+                cursor.index = Some(index);
+                text_state.set_value(entry);
+                lookup_events.write(LookupEvent::Hide);
+            }
+        }
+        if let Some(cursor) = new_cursor {
+            if cursor.index.is_some() {
+                commands.entity(id).insert(cursor);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_history() {
+        let mut history = MinibufferHistory {
+            max_len: 3,
+            ..default()
+        };
+        history.push("a", "1");
+        history.push("a", "2");
+        history.push("a", "");
+        history.push("a", "1");
+        history.push("b", "x");
+        assert_eq!(history.get("a").collect::<Vec<_>>(), vec!["1", "2"]);
+        history.push("a", "3");
+        history.push("a", "4");
+        assert_eq!(history.get("a").collect::<Vec<_>>(), vec!["4", "3", "1"]);
+        assert_eq!(history.nth("b", 0), Some("x"));
+        assert_eq!(history.nth("c", 0), None);
+    }
+
+    #[test]
+    fn record_submit() {
+        let mut world = World::new();
+        world.init_resource::<MinibufferHistory>();
+        world.add_observer(record_history);
+        let text = world.spawn((TextField, Prompt("Name: ".into()))).id();
+        let other = world
+            .spawn((
+                TextField,
+                Prompt("Other: ".into()),
+                HistoryCategory::new("name"),
+            ))
+            .id();
+        world.trigger_targets(Submit::new(Ok("Shane".to_string())), text);
+        world.trigger_targets(Submit::new(Ok("Sean".to_string())), other);
+        world.trigger_targets(Submit::<String>::new(Err(bevy_asky::Error::Cancel)), other);
+        world.flush();
+        let history = world.resource::<MinibufferHistory>();
+        assert_eq!(history.nth("Name: ", 0), Some("Shane"));
+        assert_eq!(history.get("name").collect::<Vec<_>>(), vec!["Sean"]);
+    }
+}
@@ -1,6 +1,11 @@
 //! Hotkey
-use bevy::prelude::*;
+use crate::Error;
+use bevy::{
+    prelude::*,
+    reflect::{DynamicEnum, DynamicVariant, TypeInfo, Typed, VariantInfo},
+};
 use bevy_input_sequence::KeyChord;
+use keyseq::Modifiers;
 use std::{
     borrow::Cow,
     fmt::{
@@ -8,6 +13,7 @@ use std::{
         Debug,
         // Write
     },
+    str::FromStr,
 };
 
 /// A key sequence and an optional alias
@@ -17,6 +23,8 @@ pub struct Hotkey {
     pub chords: Vec<KeyChord>,
     /// Alias
     pub alias: Option<Cow<'static, str>>,
+    /// Keymap it belongs to; the global keymap if none
+    pub keymap: Option<Cow<'static, str>>,
 }
 
 impl PartialEq<[KeyChord]> for Hotkey {
@@ -40,6 +48,7 @@ impl Hotkey {
         Self {
             chords: chords.into_iter().map(|v| v.into()).collect(),
             alias: None,
+            keymap: None,
         }
     }
 
@@ -48,6 +57,7 @@ impl Hotkey {
         Self {
             chords: Vec::new(),
             alias: None,
+            keymap: None,
         }
     }
 
@@ -74,3 +84,132 @@ impl fmt::Display for Hotkey {
         }
     }
 }
+
+/// Parse a key chord, e.g., "Ctrl-Alt-A", "Shift-;", or "Space".
+///
+/// This accepts what [KeyChord]'s `Display` implementation produces, so a chord
+/// may be written out and read back in.
+pub fn parse_chord(input: &str) -> Result<KeyChord, Error> {
+    const MODIFIERS: [(&str, Modifiers); 4] = [
+        ("Ctrl", Modifiers::CONTROL),
+        ("Alt", Modifiers::ALT),
+        ("Shift", Modifiers::SHIFT),
+        ("Super", Modifiers::SUPER),
+    ];
+    let mut mods = Modifiers::empty();
+    let mut rest = input;
+    'outer: loop {
+        for (name, modifier) in MODIFIERS {
+            // Don't consume the last dash in "Ctrl--".
+            if let Some(r) = rest
+                .strip_prefix(name)
+                .and_then(|r| r.strip_prefix('-'))
+                .filter(|r| !r.is_empty())
+            {
+                mods |= modifier;
+                rest = r;
+                continue 'outer;
+            }
+        }
+        break;
+    }
+    parse_key_code(rest)
+        .map(|key| KeyChord(mods, key))
+        .ok_or_else(|| Error::Message(format!("No such key {:?} in {:?}", rest, input).into()))
+}
+
+fn parse_key_code(input: &str) -> Option<KeyCode> {
+    use KeyCode::*;
+    let key = match input {
+        ";" => Semicolon,
+        "." => Period,
+        "=" => Equal,
+        "/" => Slash,
+        "-" => Minus,
+        "[" => BracketLeft,
+        "]" => BracketRight,
+        "'" => Quote,
+        "`" => Backquote,
+        _ => {
+            // Accept "a" as well as "A".
+            let name = if input.len() == 1 {
+                input.to_uppercase()
+            } else {
+                input.to_string()
+            };
+            let TypeInfo::Enum(info) = KeyCode::type_info() else {
+                return None;
+            };
+            return [format!("Key{name}"), format!("Digit{name}"), name]
+                .into_iter()
+                .find(|variant| matches!(info.variant(variant), Some(VariantInfo::Unit(_))))
+                .and_then(|variant| {
+                    KeyCode::from_reflect(&DynamicEnum::new(variant, DynamicVariant::Unit))
+                });
+        }
+    };
+    Some(key)
+}
+
+/// Parse a key chord sequence separated by whitespace, e.g., "Ctrl-H A". It
+/// may be prefixed by a keymap, e.g., "editor: Ctrl-S".
+impl FromStr for Hotkey {
+    type Err = Error;
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let (keymap, keys) = match input.split_once(':') {
+            Some((keymap, keys)) => (Some(keymap.trim()), keys),
+            None => (None, input),
+        };
+        if keymap.is_some_and(str::is_empty) {
+            return Err(Error::Message(format!("Empty keymap in {input:?}").into()));
+        }
+        let chords = keys
+            .split_whitespace()
+            .map(parse_chord)
+            .collect::<Result<Vec<_>, _>>()?;
+        if chords.is_empty() {
+            return Err(Error::Message("Empty key sequence".into()));
+        }
+        let mut hotkey = Hotkey::new(chords);
+        hotkey.keymap = keymap.map(|keymap| keymap.to_string().into());
+        Ok(hotkey)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input::keyseq;
+
+    #[test]
+    fn parse_chords() {
+        assert_eq!(
+            parse_chord("Ctrl-A").unwrap(),
+            KeyChord(Modifiers::CONTROL, KeyCode::KeyA)
+        );
+        assert_eq!(parse_chord("a").unwrap(), KeyChord::from(KeyCode::KeyA));
+        assert_eq!(parse_chord("1").unwrap(), KeyChord::from(KeyCode::Digit1));
+        assert_eq!(
+            parse_chord("Ctrl--").unwrap(),
+            KeyChord(Modifiers::CONTROL, KeyCode::Minus)
+        );
+        assert_eq!(
+            parse_chord("Space").unwrap(),
+            KeyChord::from(KeyCode::Space)
+        );
+        assert!(parse_chord("Ctrl-").is_err());
+        assert!(parse_chord("Hyper-A").is_err());
+    }
+
+    #[test]
+    fn parse_round_trip() {
+        let hotkey = Hotkey::new(keyseq! { Ctrl-H Alt-Shift-; Backquote F1 });
+        let parsed: Hotkey = hotkey.to_string().parse().unwrap();
+        assert_eq!(parsed.chords, hotkey.chords);
+        assert!("".parse::<Hotkey>().is_err());
+        let hotkey: Hotkey = "editor: Ctrl-S".parse().unwrap();
+        assert_eq!(hotkey.keymap.as_deref(), Some("editor"));
+        assert_eq!(hotkey.chords, Hotkey::new(keyseq! { Ctrl-S }).chords);
+        assert!(": Ctrl-S".parse::<Hotkey>().is_err());
+    }
+}
@@ -0,0 +1,131 @@
+//! Init file
+//!
+//! Like Emacs' `init.el`, an init file runs acts at startup, e.g., to turn on
+//! one's debug overlays. Each line names an act followed by its inline
+//! arguments if any.
+//!
+//! ```text
+//! # Show what's going on.
+//! toggle_debug_overlay
+//! spawn_enemy 5 "goblin king"
+//! ```
+//!
+//! Blank lines and lines that start with `#` are skipped. Arguments are parsed
+//! like those of [RunActByNameEvent] by the [ArgParserMap]. Set
+//! [Config::init_file] to run a file after `PostStartup`.
+use crate::{
+    acts::{Act, ArgParserMap},
+    event::{DispatchEvent, RunActByNameEvent},
+    plugin::MinibufferSet,
+    Config, Error,
+};
+use bevy::{ecs::schedule::common_conditions::run_once, prelude::*};
+use std::path::Path;
+
+pub(crate) fn plugin(app: &mut App) {
+    app.add_systems(
+        Update,
+        run_config_init_file
+            .run_if(run_once)
+            .in_set(MinibufferSet::Input),
+    );
+}
+
+/// Parse the contents of an init file.
+///
+/// Returns the line number, starting at 1, and the act to run of every line
+/// that is not blank or a comment.
+pub fn parse_init(contents: &str) -> Vec<(usize, Result<RunActByNameEvent, Error>)> {
+    contents
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(i, line)| (i, RunActByNameEvent::parse(line)))
+        .collect()
+}
+
+/// Run the acts of an init file in order.
+///
+/// Lines whose act does not exist or whose arguments do not parse are not run.
+/// Their errors are returned prefixed with the path and line number, e.g.,
+/// "minibuffer_init:3: No act named 'tape_lod'."
+pub fn run_init_file(path: impl AsRef<Path>, world: &mut World) -> Result<Vec<Error>, Error> {
+    let path = path.as_ref();
+    let contents = std::fs::read_to_string(path)?;
+    let mut errors = Vec::new();
+    for (line, result) in parse_init(&contents) {
+        match result.and_then(|event| check(&event, world).map(|_| event)) {
+            Ok(event) => {
+                world.trigger(event);
+            }
+            Err(e) => errors.push(Error::Message(
+                format!("{}:{line}: {e}", path.display()).into(),
+            )),
+        }
+    }
+    Ok(errors)
+}
+
+/// Check that the act exists and accepts the arguments.
+fn check(event: &RunActByNameEvent, world: &mut World) -> Result<(), Error> {
+    let mut acts = world.query::<(Entity, &Act)>();
+    let (_, act) = Act::find_by_name(acts.iter(world), &event.name)
+        .ok_or_else(|| Error::Message(format!("No act named '{}'.", event.name).into()))?;
+    if !event.args.is_empty() {
+        world
+            .get_resource::<ArgParserMap>()
+            .ok_or_else(|| Error::Message("No argument parsers.".into()))?
+            .parse(act, &event.args)?;
+    }
+    Ok(())
+}
+
+fn run_config_init_file(world: &mut World) {
+    let Some(path) = world
+        .get_resource::<Config>()
+        .and_then(|config| config.init_file.clone())
+    else {
+        return;
+    };
+    if !path.exists() {
+        info!("No init file {path:?}.");
+        return;
+    }
+    let errors = match run_init_file(&path, world) {
+        Ok(errors) => errors,
+        Err(e) => vec![e],
+    };
+    if errors.is_empty() {
+        info!("Ran init file {path:?}.");
+        return;
+    }
+    let msg = errors
+        .iter()
+        .map(|e| e.to_string())
+        .collect::<Vec<_>>()
+        .join("\n");
+    warn!("Problems running init file {path:?}: {msg}");
+    world.send_event(DispatchEvent::EmitMessage(msg));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse() {
+        let lines =
+            parse_init("# comment\n\nlist_acts\n  spawn_enemy 5 \"goblin king\"\nsay \"hi\n");
+        assert_eq!(lines.len(), 3);
+        let (line, event) = &lines[0];
+        assert_eq!(*line, 3);
+        assert_eq!(event.as_ref().unwrap().name, "list_acts");
+        let (line, event) = &lines[1];
+        assert_eq!(*line, 4);
+        assert_eq!(event.as_ref().unwrap().args, vec!["5", "goblin king"]);
+        let (line, event) = &lines[2];
+        assert_eq!(*line, 5);
+        assert!(event.is_err());
+    }
+}
@@ -6,6 +6,9 @@ pub mod autocomplete;
 pub mod event;
 #[cfg(feature = "async")]
 mod future;
+pub mod history;
+pub mod init_file;
+pub mod pager;
 mod plugin;
 pub mod prompt;
 mod sync;
@@ -15,7 +18,10 @@ pub use plugin::Error;
 pub use plugin::MinibufferPlugin;
 pub use plugin::MinibufferPlugins;
 pub mod sink;
+#[cfg(feature = "testing")]
+pub mod testing;
 pub mod view;
+pub mod which_key;
 #[cfg(feature = "async")]
 pub use future::MinibufferAsync;
 pub use sync::Minibuffer;
@@ -34,7 +40,12 @@ pub mod input {
 /// Prelude for convenient splat importing, e.g., `use bevy_minibuffer::prelude::*`.
 pub mod prelude {
     pub use super::acts::basic::BasicActs;
+    pub use super::acts::binding::BindingActs;
+    pub use super::acts::keymaps::{AddKeymaps, Keymaps};
+    pub use super::acts::resource::ResourceActs;
+    pub use super::acts::state::StateActs;
     pub use super::acts::tape::TapeActs;
+    pub use super::acts::undo::UndoActs;
     pub use super::acts::universal::*;
     pub use super::acts::{
         self, Act, ActBuilder, ActFlags, Acts, ActsPlugin, ActsPluginGroup, AddActs,
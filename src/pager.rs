@@ -0,0 +1,261 @@
+//! Pager
+//!
+//! Like `less`, a pager shows long output a page at a time in the minibuffer.
+//! [Minibuffer::page] opens one; if [Config::pager_lines] is set, any message
+//! with more lines than that opens one too.
+//!
+//! | Key                           | Action              |
+//! |-------------------------------|---------------------|
+//! | `Space`, `PageDown`, `Ctrl-V` | Next page           |
+//! | `PageUp`, `Backspace`, `Alt-V`| Previous page       |
+//! | `Down`, `Enter`, `J`          | Next line           |
+//! | `Up`, `K`                     | Previous line       |
+//! | `Home`, `End`                 | First or last page  |
+//! | `/`                           | Search              |
+//! | `N`                           | Search again        |
+//! | `Q`, `Escape`                 | Dismiss             |
+#[cfg(doc)]
+use crate::Config;
+use crate::{
+    event::KeyChordEvent,
+    input::{KeyChord, Modifiers},
+    prompt::{GetKeyChord, Submit, TextField},
+    Minibuffer,
+};
+use bevy::prelude::*;
+
+/// Lines per page if [Config::pager_lines] is not set
+pub const DEFAULT_PAGE_LINES: usize = 20;
+
+/// The state of a pager
+#[derive(Component, Debug, Clone)]
+pub struct Pager {
+    lines: Vec<String>,
+    top: usize,
+    height: usize,
+    search: Option<String>,
+    /// The line last found, where searching again starts
+    found: Option<usize>,
+    not_found: bool,
+}
+
+impl Pager {
+    /// Page `text` showing `height` lines at a time.
+    pub fn new(text: &str, height: usize) -> Self {
+        Self {
+            lines: text.lines().map(String::from).collect(),
+            top: 0,
+            height: height.max(1),
+            search: None,
+            found: None,
+            not_found: false,
+        }
+    }
+
+    /// Return the index of the first line shown.
+    pub fn top(&self) -> usize {
+        self.top
+    }
+
+    /// Return the lines shown.
+    pub fn page(&self) -> &[String] {
+        let end = (self.top + self.height).min(self.lines.len());
+        &self.lines[self.top..end]
+    }
+
+    fn last_top(&self) -> usize {
+        self.lines.len().saturating_sub(self.height)
+    }
+
+    /// Show the page starting at line `top`.
+    pub fn scroll_to(&mut self, top: usize) {
+        self.top = top.min(self.last_top());
+        self.found = None;
+        self.not_found = false;
+    }
+
+    /// Scroll by `lines`, down if positive, up if negative.
+    pub fn scroll(&mut self, lines: isize) {
+        self.scroll_to(self.top.saturating_add_signed(lines));
+    }
+
+    /// Show the next page.
+    pub fn page_down(&mut self) {
+        self.scroll(self.height as isize);
+    }
+
+    /// Show the previous page.
+    pub fn page_up(&mut self) {
+        self.scroll(-(self.height as isize));
+    }
+
+    /// Scroll to the first line from the top that contains `query`, wrapping
+    /// around to the start. Return true if one was found.
+    pub fn search(&mut self, query: impl Into<String>) -> bool {
+        self.search = Some(query.into());
+        self.found = None;
+        self.search_next()
+    }
+
+    /// Search again for the last query after the line last found.
+    pub fn search_next(&mut self) -> bool {
+        let Some(query) = self.search.as_deref() else {
+            return false;
+        };
+        let start = self.found.map(|i| i + 1).unwrap_or(self.top);
+        let len = self.lines.len();
+        let found = (0..len)
+            .map(|i| (start + i) % len)
+            .find(|i| self.lines[*i].contains(query));
+        if let Some(i) = found {
+            self.scroll_to(i);
+            self.found = Some(i);
+        }
+        self.not_found = found.is_none();
+        found.is_some()
+    }
+
+    /// Return the status line, e.g., "-- Lines 1-20 of 45 --".
+    pub fn status(&self) -> String {
+        let mut status = format!(
+            "-- Lines {}-{} of {} --",
+            (self.top + 1).min(self.lines.len()),
+            self.top + self.page().len(),
+            self.lines.len()
+        );
+        if self.not_found {
+            if let Some(ref query) = self.search {
+                status.push_str(&format!(" Not found: {query}"));
+            }
+        }
+        status
+    }
+
+    /// Return the page followed by the status line.
+    pub fn view(&self) -> String {
+        let mut view = self.page().join("\n");
+        view.push('\n');
+        view.push_str(&self.status());
+        view
+    }
+}
+
+/// Despawn any other pagers, so only one takes key chords.
+pub(crate) fn despawn_others(id: Entity, world: &mut World) {
+    let mut query = world.query_filtered::<Entity, With<Pager>>();
+    let others: Vec<Entity> = query.iter(world).filter(|other| *other != id).collect();
+    for other in others {
+        world.despawn(other);
+    }
+}
+
+/// Handle the pager's keys.
+pub(crate) fn pager_keys(
+    mut trigger: Trigger<KeyChordEvent>,
+    mut pagers: Query<&mut Pager>,
+    mut minibuffer: Minibuffer,
+) {
+    let id = trigger.target();
+    let Ok(mut pager) = pagers.get_mut(id) else {
+        return;
+    };
+    let Ok(KeyChord(mods, key)) = trigger.event_mut().take() else {
+        // Canceled
+        dismiss(id, &mut minibuffer);
+        return;
+    };
+    match key {
+        KeyCode::Space | KeyCode::PageDown => pager.page_down(),
+        KeyCode::KeyV if mods == Modifiers::CONTROL => pager.page_down(),
+        KeyCode::KeyV if mods == Modifiers::ALT => pager.page_up(),
+        KeyCode::PageUp | KeyCode::Backspace => pager.page_up(),
+        KeyCode::ArrowDown | KeyCode::Enter | KeyCode::KeyJ => pager.scroll(1),
+        KeyCode::ArrowUp | KeyCode::KeyK => pager.scroll(-1),
+        KeyCode::Home => pager.scroll_to(0),
+        KeyCode::End => pager.scroll_to(usize::MAX),
+        KeyCode::KeyN => {
+            pager.search_next();
+        }
+        KeyCode::Slash => {
+            prompt_search(id, &mut minibuffer);
+            return;
+        }
+        KeyCode::KeyQ => {
+            dismiss(id, &mut minibuffer);
+            return;
+        }
+        _ => return,
+    }
+    minibuffer.show_text(pager.view());
+}
+
+fn dismiss(id: Entity, minibuffer: &mut Minibuffer) {
+    minibuffer.commands.entity(id).despawn();
+    minibuffer.clear();
+}
+
+/// Ask for a query. The pager does not take key chords meanwhile.
+fn prompt_search(id: Entity, minibuffer: &mut Minibuffer) {
+    minibuffer.commands.entity(id).remove::<GetKeyChord>();
+    minibuffer.prompt::<TextField>("/").observe(
+        move |mut trigger: Trigger<Submit<String>>,
+              mut pagers: Query<&mut Pager>,
+              mut minibuffer: Minibuffer| {
+            let Ok(mut pager) = pagers.get_mut(id) else {
+                return;
+            };
+            if let Ok(query) = trigger.event_mut().take_result() {
+                pager.search(query);
+            }
+            minibuffer.commands.entity(id).insert(GetKeyChord);
+            minibuffer.show_text(pager.view());
+        },
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(n: usize) -> String {
+        (1..=n)
+            .map(|i| format!("line {i}"))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn scroll() {
+        let mut pager = Pager::new(&text(10), 4);
+        assert_eq!(pager.page(), ["line 1", "line 2", "line 3", "line 4"]);
+        assert_eq!(pager.status(), "-- Lines 1-4 of 10 --");
+        pager.page_down();
+        assert_eq!(pager.top(), 4);
+        pager.page_down();
+        assert_eq!(pager.top(), 6, "The last page is full.");
+        assert_eq!(pager.status(), "-- Lines 7-10 of 10 --");
+        pager.scroll(-1);
+        assert_eq!(pager.top(), 5);
+        pager.page_up();
+        pager.page_up();
+        assert_eq!(pager.top(), 0);
+
+        let pager = Pager::new("", 4);
+        assert!(pager.page().is_empty());
+        assert_eq!(pager.status(), "-- Lines 0-0 of 0 --");
+    }
+
+    #[test]
+    fn search() {
+        let mut pager = Pager::new(&text(10), 4);
+        assert!(pager.search("line 3"));
+        assert_eq!(pager.top(), 2);
+        assert!(pager.search("line 1"));
+        assert_eq!(pager.top(), 6, "line 10 is after line 3; clamped.");
+        assert!(pager.search_next());
+        assert_eq!(pager.top(), 0, "Wraps around to line 1.");
+        assert!(!pager.search("line 11"));
+        assert_eq!(pager.top(), 0);
+        assert_eq!(pager.status(), "-- Lines 1-4 of 10 -- Not found: line 11");
+    }
+}
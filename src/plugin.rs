@@ -1,4 +1,5 @@
 use crate::{
+    acts::DuplicateActPolicy,
     autocomplete::LookupError,
     event::{dispatch_events, run_acts, run_acts_by_name, KeyChordEvent, LookupEvent},
     prompt::{
@@ -14,7 +15,7 @@ use bevy::{
         SystemSet,
         // on_event,
     },
-    prelude::{OnEnter, OnExit, PluginGroup, Resource},
+    prelude::{OnEnter, OnExit, PluginGroup, ReflectResource, Resource},
     reflect::Reflect,
     state::{
         app::AppExtStates,
@@ -24,7 +25,7 @@ use bevy::{
 };
 use bevy_asky::{AskyPlugin, AskySet};
 use bevy_input_sequence::InputSequencePlugin;
-use std::{borrow::Cow, time::Duration};
+use std::{borrow::Cow, path::PathBuf, time::Duration};
 
 /// Minibuffer plugin
 #[derive(Debug, Default, Clone)]
@@ -49,11 +50,38 @@ impl PluginGroup for MinibufferPlugins {
 
 /// Minibuffer config
 #[derive(Debug, Resource, Clone, Default, Reflect)]
+#[reflect(Resource)]
 pub struct Config {
     /// If true, auto hide minibuffer after use.
     pub auto_hide: bool,
     /// Auto hide delay.
     pub hide_delay: Duration,
+    /// If true, 'run_act' matches act names fuzzily. See [FuzzyLookup].
+    ///
+    /// [FuzzyLookup]: crate::autocomplete::FuzzyLookup
+    pub fuzzy_match: bool,
+    /// If true, 'run_act' accepts inline arguments like `spawn_enemy 5
+    /// "goblin"`. See [ArgParserMap].
+    ///
+    /// [ArgParserMap]: crate::acts::ArgParserMap
+    pub inline_args: bool,
+    /// What to do when an act is added with the name of an existing act.
+    pub duplicate_acts: DuplicateActPolicy,
+    /// Run the acts of this file at startup, e.g., "minibuffer_init". See
+    /// [init_file].
+    ///
+    /// [init_file]: crate::init_file
+    pub init_file: Option<PathBuf>,
+    /// If set, list what may follow a key sequence prefix after it has been
+    /// pending this long. See [which_key].
+    ///
+    /// [which_key]: crate::which_key
+    pub which_key: Option<Duration>,
+    /// If set, a message with more lines than this is shown in a pager this
+    /// many lines at a time. See [pager].
+    ///
+    /// [pager]: crate::pager
+    pub pager_lines: Option<usize>,
 }
 
 /// Minibuffer error
@@ -68,6 +96,18 @@ pub enum Error {
     /// An Asky error
     #[error("{0}")]
     Asky(#[from] bevy_asky::Error),
+    /// A key binding conflict
+    #[error("{0}")]
+    Conflict(#[from] crate::acts::Conflict),
+    /// An IO error
+    #[error("io error {0}")]
+    Io(#[from] std::io::Error),
+    /// A RON error
+    #[error("ron error {0}")]
+    Ron(#[from] ron::Error),
+    /// A RON parse error with its position
+    #[error("ron error {0}")]
+    RonSpanned(#[from] ron::error::SpannedError),
     /// An async error
     #[cfg(feature = "async")]
     #[error("async error {0}")]
@@ -100,10 +140,13 @@ impl bevy::app::Plugin for MinibufferPlugin {
             .register_type::<PromptState>()
             .register_type::<CompletionState>()
             .register_type::<Config>()
+            .register_type::<DuplicateActPolicy>()
             .add_plugins(crate::ui::plugin)
             .add_plugins(crate::event::plugin)
             .add_plugins(crate::prompt::plugin)
             .add_plugins(crate::autocomplete::plugin)
+            .add_plugins(crate::history::plugin)
+            .add_plugins(crate::init_file::plugin)
             .add_plugins(crate::view::plugin)
             .add_plugins(crate::acts::plugin)
             .add_plugins(AskyPlugin)
@@ -124,6 +167,9 @@ impl bevy::app::Plugin for MinibufferPlugin {
                           (run_acts_by_name, run_acts, prompt::set_minibuffer_state).chain(),
                           (dispatch_events, lookup_events).chain())
                          .in_set(MinibufferSet::Process))
+            .add_systems(Update, crate::which_key::which_key
+                         .after(InputSequenceSet)
+                         .run_if(in_state(MinibufferState::Inactive)))
             .add_systems(OnEnter(MinibufferState::Inactive),hide_delayed::<ui::BottomBar>)
             .add_systems(OnEnter(MinibufferState::Inactive),hide::<ui::CompletionContainer>)
             .add_systems(OnEnter(PromptState::Visible),     show::<ui::BottomBar>)
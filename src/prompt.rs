@@ -1,7 +1,7 @@
 //! Kinds of queries for user: Toggle, TextField, etc.
 
 use crate::{
-    event::{KeyChordEvent, LookupEvent},
+    event::{Completion, KeyChordEvent, LookupEvent},
     ui::{completion_item, ScrollingList},
     Config,
 };
@@ -188,12 +188,12 @@ pub(crate) fn hide<T: Component>(
 fn completion_set(
     completion: Entity,
     children: Option<&Children>,
-    labels: Vec<String>,
+    completions: Vec<Completion>,
     commands: &mut Commands,
 ) {
-    let new_children = labels
+    let new_children = completions
         .into_iter()
-        .map(|label| commands.spawn(completion_item(label)).id())
+        .map(|completion| commands.spawn(completion_item(completion)).id())
         .collect::<Vec<Entity>>();
     commands.entity(completion).replace_children(&new_children);
     if let Some(children) = children {
@@ -205,7 +205,7 @@ fn completion_set(
 
 pub(crate) fn lookup_events(
     mut lookup_events: EventReader<LookupEvent>,
-    completion: Query<(Entity, Option<&Children>), With<ScrollingList>>,
+    mut completion: Query<(Entity, Option<&Children>, &mut ScrollingList)>,
     mut next_completion_state: ResMut<NextState<CompletionState>>,
     mut redraw: EventWriter<RequestRedraw>,
     mut commands: Commands,
@@ -224,7 +224,8 @@ pub(crate) fn lookup_events(
                 let hash = rnd_state.finish();
                 // eprintln!("hash {hash}");
                 if last_hash.unwrap_or(0) != hash {
-                    if let Ok((completion_node, children)) = completion.single() {
+                    if let Ok((completion_node, children, mut list)) = completion.single_mut() {
+                        list.set_labels(v.iter().map(|x| x.label.clone()).collect());
                         completion_set(completion_node, children, v.clone(), &mut commands);
                         next_completion_state.set(CompletionState::Visible);
                         redraw.write(RequestRedraw);
@@ -235,6 +236,9 @@ pub(crate) fn lookup_events(
             LookupEvent::Hide => {
                 // eprintln!("hide");
                 *last_hash = None;
+                if let Ok((_, _, mut list)) = completion.single_mut() {
+                    list.selection = None;
+                }
                 next_completion_state.set(CompletionState::Invisible);
                 redraw.write(RequestRedraw);
             }
@@ -4,13 +4,18 @@
 #[cfg(all(doc, feature = "async"))]
 use crate::future::MinibufferAsync;
 use crate::{
-    acts::{tape::TapeRecorder, ActArg},
-    autocomplete::{AutoComplete, Completed, Lookup, LookupMap, RequireMatch},
-    event::{RunActByNameEvent, RunActEvent},
+    acts::{
+        tape::{RunActRecord, TapeRecorder},
+        undo::UndoStack,
+        ActArg,
+    },
+    autocomplete::{AutoComplete, Completed, Lookup, LookupMap, RequireMatch, VariantLookup},
+    event::{LastRunAct, RunActByNameEvent, RunActEvent},
+    pager::{self, Pager},
     prompt::{GetKeyChord, PromptState},
     ui::PromptContainer,
     view::View,
-    Error,
+    Config, Error,
 };
 use bevy::{
     ecs::{
@@ -18,14 +23,16 @@ use bevy::{
         entity::Entity,
         prelude::Commands,
         query::With,
-        system::{EntityCommands, Query, SystemParam},
+        system::{Command, EntityCommands, Query, SystemParam},
     },
     prelude::{
         default, Children, LineBreak, NextState, Res, ResMut, State, Text, TextLayout, Trigger,
+        World,
     },
+    reflect::{FromReflect, Typed},
 };
 use bevy_asky::{prelude::*, sync::AskyCommands, Dest, Part};
-use std::fmt::Debug;
+use std::{borrow::Cow, fmt::Debug};
 
 /// Manipulate minibuffer synchronously with this [SystemParam].
 ///
@@ -50,6 +57,12 @@ pub struct Minibuffer<'w, 's> {
     next_prompt_state: ResMut<'w, NextState<PromptState>>,
     /// macro state
     pub(crate) tape_recorder: ResMut<'w, TapeRecorder>,
+    /// undo and redo stacks
+    undo_stack: ResMut<'w, UndoStack>,
+    /// the act being run
+    last_run_act: Res<'w, LastRunAct>,
+    /// config
+    config: Option<Res<'w, Config>>,
 }
 
 /// An [EntityCommands] extension trait
@@ -58,7 +71,7 @@ pub trait MinibufferCommands {
     fn prompt_children<T: Construct + Component + Part>(
         &mut self,
         props: impl IntoIterator<Item = impl Into<T::Props>>,
-    ) -> EntityCommands
+    ) -> EntityCommands<'_>
     where
         <T as Construct>::Props: Send;
 }
@@ -67,7 +80,7 @@ impl MinibufferCommands for EntityCommands<'_> {
     fn prompt_children<T: Construct + Component + Part>(
         &mut self,
         props: impl IntoIterator<Item = impl Into<T::Props>>,
-    ) -> EntityCommands
+    ) -> EntityCommands<'_>
     where
         <T as Construct>::Props: Send,
     {
@@ -81,7 +94,7 @@ impl Minibuffer<'_, '_> {
     pub fn prompt<T: Construct + Component + Submitter>(
         &mut self,
         props: impl Into<T::Props>,
-    ) -> EntityCommands
+    ) -> EntityCommands<'_>
     where
         <T as Construct>::Props: Send,
         <T as Submitter>::Out: Clone + Debug + Send + Sync,
@@ -102,7 +115,38 @@ impl Minibuffer<'_, '_> {
     // }
 
     /// Leave a message in the minibuffer.
+    ///
+    /// If it has more lines than [Config::pager_lines], it is shown in a
+    /// pager.
     pub fn message(&mut self, msg: impl Into<String>) {
+        let msg = msg.into();
+        let limit = self.config.as_ref().and_then(|config| config.pager_lines);
+        if limit.is_some_and(|limit| msg.lines().count() > limit) {
+            self.page(msg);
+        } else {
+            self.show_text(msg);
+        }
+    }
+
+    /// Show long text a page at a time. See [pager].
+    pub fn page(&mut self, text: impl Into<String>) -> EntityCommands<'_> {
+        let height = self
+            .config
+            .as_ref()
+            .and_then(|config| config.pager_lines)
+            .unwrap_or(pager::DEFAULT_PAGE_LINES);
+        let pager = Pager::new(&text.into(), height);
+        self.show_text(pager.view());
+        let id = self.commands.spawn((pager, GetKeyChord)).id();
+        self.commands
+            .queue(move |world: &mut World| pager::despawn_others(id, world));
+        let mut commands = self.commands.entity(id);
+        commands.observe(pager::pager_keys);
+        commands
+    }
+
+    /// Show text in the minibuffer as is.
+    pub(crate) fn show_text(&mut self, msg: impl Into<String>) {
         let msg = msg.into();
         let dest = self.dest.single().expect("minibuffer dest");
         if let Some(mut commands) = Dest::ReplaceChildren(dest).get_entity(&mut self.commands) {
@@ -155,6 +199,37 @@ impl Minibuffer<'_, '_> {
 
     pub fn log_input<I: Debug + Clone + Send + Sync + 'static>(&mut self, input: &I) {
         self.tape_recorder.process_input(input);
+        self.undo_stack
+            .ammend_input(self.tape_recorder.last_record());
+    }
+
+    /// Push an inverse of what the current act did, so the 'undo' act can
+    /// reverse it.
+    ///
+    /// The 'redo' act runs the current act again with its logged input. See
+    /// [UndoActs].
+    ///
+    /// [UndoActs]: crate::acts::undo::UndoActs
+    pub fn push_undo(
+        &mut self,
+        description: impl Into<Cow<'static, str>>,
+        undo: impl Command + Sync,
+    ) {
+        let record = self.current_record();
+        self.undo_stack.push(description, record, undo);
+    }
+
+    /// Return the record of the current act's run, which 'redo' replays.
+    pub(crate) fn current_record(&self) -> Option<RunActRecord> {
+        // Only keep the tape's record if it is this act's.
+        self.tape_recorder
+            .last_record()
+            .filter(|record| {
+                self.last_run_act
+                    .event()
+                    .is_some_and(|event| event.act.id == record.act.id)
+            })
+            .cloned()
     }
 
     /// Read input from user with autocomplete provided by a [Lookup].
@@ -162,7 +237,7 @@ impl Minibuffer<'_, '_> {
         &mut self,
         prompt: impl Into<<TextField as Construct>::Props>,
         lookup: L,
-    ) -> EntityCommands
+    ) -> EntityCommands<'_>
     where
         L: Lookup + Send + Sync + 'static,
     {
@@ -179,7 +254,7 @@ impl Minibuffer<'_, '_> {
         &mut self,
         prompt: impl Into<<TextField as Construct>::Props>,
         lookup: L,
-    ) -> EntityCommands
+    ) -> EntityCommands<'_>
     where
         L: Lookup + Clone + LookupMap + Send + Sync + 'static,
         <L as LookupMap>::Item: Sync + Debug,
@@ -210,6 +285,21 @@ impl Minibuffer<'_, '_> {
         ecommands
     }
 
+    /// Read a unit variant of a [Reflect] enum by name.
+    ///
+    /// Instead of triggering [`Submit<String>`] it will trigger [`Completed<T>`].
+    ///
+    /// [Reflect]: bevy::reflect::Reflect
+    pub fn prompt_enum<T>(
+        &mut self,
+        prompt: impl Into<<TextField as Construct>::Props>,
+    ) -> EntityCommands<'_>
+    where
+        T: FromReflect + Typed + Debug + Send + Sync,
+    {
+        self.prompt_map(prompt, VariantLookup::<T>::new())
+    }
+
     /// Clear the minibuffer.
     pub fn clear(&mut self) {
         let dest = self.dest.single().expect("minibuffer dest");
@@ -231,7 +321,7 @@ impl Minibuffer<'_, '_> {
     }
 
     /// Get the next key chord.
-    pub fn get_chord(&mut self) -> EntityCommands {
+    pub fn get_chord(&mut self) -> EntityCommands<'_> {
         self.commands.spawn(GetKeyChord)
     }
 }
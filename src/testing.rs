@@ -0,0 +1,373 @@
+//! Drive Minibuffer headlessly in integration tests
+//!
+//! ```
+//! use bevy_minibuffer::{prelude::*, prompt::MinibufferState, testing::*};
+//! let mut app = headless_app();
+//! app.add_acts(BasicActs::default());
+//! app.advance(2);
+//! app.press("Shift-;").type_text("list_acts").press("Enter");
+//! assert_eq!(app.minibuffer_state(), MinibufferState::Inactive);
+//! assert!(app.message().unwrap().contains("list_acts"));
+//! ```
+use crate::{
+    input::{Hotkey, KeyChord, Modifiers},
+    prompt::MinibufferState,
+    ui::PromptContainer,
+    MinibufferPlugins,
+};
+use bevy::{
+    input::{
+        keyboard::{Key, KeyboardInput, NativeKey, NativeKeyCode},
+        ButtonState, InputPlugin,
+    },
+    prelude::*,
+    state::app::StatesPlugin,
+    text::TextPlugin,
+    time::TimeUpdateStrategy,
+    window::RequestRedraw,
+};
+use bevy_asky::{prompt::Prompt, string_cursor::StringCursor};
+use std::time::Duration;
+
+/// How much time passes each update
+pub const FRAME: Duration = Duration::from_nanos(1_000_000_000 / 60);
+
+/// Add what Minibuffer needs to run without a window or renderer.
+///
+/// Add this plugin after [MinimalPlugins] and before [MinibufferPlugins]. Each
+/// update advances time by [FRAME].
+/// Plugins that are already present are not added again.
+pub struct HeadlessPlugin;
+
+impl Plugin for HeadlessPlugin {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<AssetPlugin>() {
+            app.add_plugins(AssetPlugin::default());
+        }
+        // Key sequences are only read if `ButtonInput<KeyCode>` exists when
+        // they are built.
+        if !app.is_plugin_added::<InputPlugin>() {
+            app.add_plugins(InputPlugin);
+        }
+        if !app.is_plugin_added::<StatesPlugin>() {
+            app.add_plugins(StatesPlugin);
+        }
+        // Advance time by a frame at 60 Hz each update. This makes runs
+        // repeatable and lets events expire as they would in a game, since
+        // they are cleared when the fixed timestep runs.
+        app.insert_resource(TimeUpdateStrategy::ManualDuration(FRAME))
+            .add_event::<RequestRedraw>()
+            .init_asset::<Image>()
+            .init_asset::<TextureAtlasLayout>();
+        if !app.is_plugin_added::<TextPlugin>() {
+            app.add_plugins(TextPlugin);
+        }
+        #[cfg(feature = "fun")]
+        app.init_asset::<bevy::audio::AudioSource>();
+    }
+}
+
+/// Return an app with [MinimalPlugins], [HeadlessPlugin], and
+/// [MinibufferPlugins].
+pub fn headless_app() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, HeadlessPlugin, MinibufferPlugins));
+    app
+}
+
+/// Frames to run after input so focus and state changes take effect
+const SETTLE_FRAMES: usize = 2;
+
+/// Drive Minibuffer from an [App] as a user would
+///
+/// Each key is pressed for one frame and released the next. After pressing or
+/// typing, a few more frames are run so [MinibufferState] is up to date.
+pub trait MinibufferTestExt {
+    /// Press a key chord sequence, e.g., "Ctrl-H A" or "Shift-;".
+    ///
+    /// Panics if the keys do not parse; see [Hotkey]'s `FromStr`.
+    fn press(&mut self, keys: &str) -> &mut Self;
+    /// Press key chords.
+    fn press_chords(&mut self, chords: impl IntoIterator<Item = KeyChord>) -> &mut Self;
+    /// Type text into the focused prompt.
+    fn type_text(&mut self, text: &str) -> &mut Self;
+    /// Run `frames` updates.
+    fn advance(&mut self, frames: usize) -> &mut Self;
+    /// Return the message shown in the minibuffer if there is one.
+    fn message(&mut self) -> Option<String>;
+    /// Return the prompt shown in the minibuffer if there is one, e.g., ":".
+    fn prompt(&mut self) -> Option<String>;
+    /// Return what has been typed into the prompt if there is one.
+    fn input(&mut self) -> Option<String>;
+    /// Return the current [MinibufferState].
+    fn minibuffer_state(&self) -> MinibufferState;
+}
+
+impl MinibufferTestExt for App {
+    #[track_caller]
+    fn press(&mut self, keys: &str) -> &mut Self {
+        let hotkey: Hotkey = keys
+            .parse()
+            .unwrap_or_else(|e| panic!("Could not parse keys {keys:?}: {e}"));
+        self.press_chords(hotkey.chords)
+    }
+
+    fn press_chords(&mut self, chords: impl IntoIterator<Item = KeyChord>) -> &mut Self {
+        for KeyChord(mods, key) in chords {
+            tap(self, key, logical_key(key, mods), mods);
+        }
+        self.advance(SETTLE_FRAMES)
+    }
+
+    fn type_text(&mut self, text: &str) -> &mut Self {
+        for c in text.chars() {
+            let logical_key = if c == ' ' {
+                Key::Space
+            } else {
+                Key::Character(c.to_string().into())
+            };
+            tap(self, key_code_of_char(c), logical_key, Modifiers::empty());
+        }
+        self.advance(SETTLE_FRAMES)
+    }
+
+    fn advance(&mut self, frames: usize) -> &mut Self {
+        for _ in 0..frames {
+            self.update();
+        }
+        self
+    }
+
+    fn message(&mut self) -> Option<String> {
+        let world = self.world_mut();
+        let mut query = world.query::<(&Text, &ChildOf, Has<Prompt>)>();
+        let mut containers = world.query_filtered::<Entity, With<PromptContainer>>();
+        let container = containers.single(world).ok()?;
+        query
+            .iter(world)
+            .find(|(_, child_of, is_prompt)| child_of.parent() == container && !is_prompt)
+            .map(|(text, _, _)| text.0.clone())
+    }
+
+    fn prompt(&mut self) -> Option<String> {
+        prompt_child(self.world_mut(), |prompt, _| prompt.0.to_string())
+    }
+
+    fn input(&mut self) -> Option<String> {
+        prompt_child(self.world_mut(), |_, cursor| {
+            cursor.map(|cursor| cursor.value.clone())
+        })
+        .flatten()
+    }
+
+    fn minibuffer_state(&self) -> MinibufferState {
+        **self.world().resource::<State<MinibufferState>>()
+    }
+}
+
+/// Map the prompt that is a child of the [PromptContainer].
+fn prompt_child<T>(
+    world: &mut World,
+    f: impl FnOnce(&Prompt, Option<&StringCursor>) -> T,
+) -> Option<T> {
+    let mut containers = world.query_filtered::<Entity, With<PromptContainer>>();
+    let container = containers.single(world).ok()?;
+    let mut query = world.query::<(&Prompt, Option<&StringCursor>, &ChildOf)>();
+    query
+        .iter(world)
+        .find(|(_, _, child_of)| child_of.parent() == container)
+        .map(|(prompt, cursor, _)| f(prompt, cursor))
+}
+
+/// Press a key with modifiers for one frame and release it the next.
+fn tap(app: &mut App, key: KeyCode, logical_key: Key, mods: Modifiers) {
+    let mod_keys = modifier_keys(mods);
+    // Like a keyboard, only produce text when no modifier other than shift is
+    // held.
+    let typed = (mods - Modifiers::SHIFT).is_empty();
+    let world = app.world_mut();
+    for mod_key in &mod_keys {
+        world.send_event(keyboard_input(
+            *mod_key,
+            logical_key_of_modifier(*mod_key),
+            ButtonState::Pressed,
+            false,
+        ));
+    }
+    world.send_event(keyboard_input(
+        key,
+        logical_key.clone(),
+        ButtonState::Pressed,
+        typed,
+    ));
+    app.update();
+    let world = app.world_mut();
+    world.send_event(keyboard_input(
+        key,
+        logical_key,
+        ButtonState::Released,
+        false,
+    ));
+    for mod_key in mod_keys.into_iter().rev() {
+        world.send_event(keyboard_input(
+            mod_key,
+            logical_key_of_modifier(mod_key),
+            ButtonState::Released,
+            false,
+        ));
+    }
+    app.update();
+}
+
+fn keyboard_input(
+    key_code: KeyCode,
+    logical_key: Key,
+    state: ButtonState,
+    typed: bool,
+) -> KeyboardInput {
+    let text = match &logical_key {
+        Key::Character(s) if typed => Some(s.clone()),
+        Key::Space if typed => Some(" ".into()),
+        _ => None,
+    };
+    KeyboardInput {
+        key_code,
+        logical_key,
+        state,
+        text,
+        repeat: false,
+        window: Entity::PLACEHOLDER,
+    }
+}
+
+fn modifier_keys(mods: Modifiers) -> Vec<KeyCode> {
+    [
+        (Modifiers::CONTROL, KeyCode::ControlLeft),
+        (Modifiers::ALT, KeyCode::AltLeft),
+        (Modifiers::SHIFT, KeyCode::ShiftLeft),
+        (Modifiers::SUPER, KeyCode::SuperLeft),
+    ]
+    .into_iter()
+    .filter(|(modifier, _)| mods.contains(*modifier))
+    .map(|(_, key)| key)
+    .collect()
+}
+
+fn logical_key_of_modifier(key: KeyCode) -> Key {
+    match key {
+        KeyCode::ControlLeft => Key::Control,
+        KeyCode::AltLeft => Key::Alt,
+        KeyCode::ShiftLeft => Key::Shift,
+        _ => Key::Super,
+    }
+}
+
+/// Characters of the keys that produce one on a US layout: (key, plain, shifted).
+const CHARACTERS: [(KeyCode, char, char); 21] = [
+    (KeyCode::Digit0, '0', ')'),
+    (KeyCode::Digit1, '1', '!'),
+    (KeyCode::Digit2, '2', '@'),
+    (KeyCode::Digit3, '3', '#'),
+    (KeyCode::Digit4, '4', '$'),
+    (KeyCode::Digit5, '5', '%'),
+    (KeyCode::Digit6, '6', '^'),
+    (KeyCode::Digit7, '7', '&'),
+    (KeyCode::Digit8, '8', '*'),
+    (KeyCode::Digit9, '9', '('),
+    (KeyCode::Minus, '-', '_'),
+    (KeyCode::Equal, '=', '+'),
+    (KeyCode::BracketLeft, '[', '{'),
+    (KeyCode::BracketRight, ']', '}'),
+    (KeyCode::Backslash, '\\', '|'),
+    (KeyCode::Semicolon, ';', ':'),
+    (KeyCode::Quote, '\'', '"'),
+    (KeyCode::Comma, ',', '<'),
+    (KeyCode::Period, '.', '>'),
+    (KeyCode::Slash, '/', '?'),
+    (KeyCode::Backquote, '`', '~'),
+];
+
+/// Return the logical key a key code produces on a US layout.
+fn logical_key(key: KeyCode, mods: Modifiers) -> Key {
+    let shift = mods.contains(Modifiers::SHIFT);
+    match key {
+        KeyCode::Space => Key::Space,
+        KeyCode::Enter => Key::Enter,
+        KeyCode::Escape => Key::Escape,
+        KeyCode::Tab => Key::Tab,
+        KeyCode::Backspace => Key::Backspace,
+        KeyCode::Delete => Key::Delete,
+        KeyCode::ArrowUp => Key::ArrowUp,
+        KeyCode::ArrowDown => Key::ArrowDown,
+        KeyCode::ArrowLeft => Key::ArrowLeft,
+        KeyCode::ArrowRight => Key::ArrowRight,
+        KeyCode::Home => Key::Home,
+        KeyCode::End => Key::End,
+        key => {
+            if let Some(c) = letter(key) {
+                let c = if shift { c.to_ascii_uppercase() } else { c };
+                return Key::Character(c.to_string().into());
+            }
+            CHARACTERS
+                .iter()
+                .find(|(k, _, _)| *k == key)
+                .map(|(_, plain, shifted)| {
+                    Key::Character(if shift { shifted } else { plain }.to_string().into())
+                })
+                .unwrap_or(Key::Unidentified(NativeKey::Unidentified))
+        }
+    }
+}
+
+/// Letter keys in alphabetical order
+const LETTERS: [KeyCode; 26] = [
+    KeyCode::KeyA,
+    KeyCode::KeyB,
+    KeyCode::KeyC,
+    KeyCode::KeyD,
+    KeyCode::KeyE,
+    KeyCode::KeyF,
+    KeyCode::KeyG,
+    KeyCode::KeyH,
+    KeyCode::KeyI,
+    KeyCode::KeyJ,
+    KeyCode::KeyK,
+    KeyCode::KeyL,
+    KeyCode::KeyM,
+    KeyCode::KeyN,
+    KeyCode::KeyO,
+    KeyCode::KeyP,
+    KeyCode::KeyQ,
+    KeyCode::KeyR,
+    KeyCode::KeyS,
+    KeyCode::KeyT,
+    KeyCode::KeyU,
+    KeyCode::KeyV,
+    KeyCode::KeyW,
+    KeyCode::KeyX,
+    KeyCode::KeyY,
+    KeyCode::KeyZ,
+];
+
+/// Return the lowercase letter of a key code.
+fn letter(key: KeyCode) -> Option<char> {
+    LETTERS
+        .iter()
+        .position(|k| *k == key)
+        .map(|i| (b'a' + i as u8) as char)
+}
+
+/// Return the key code that types a character on a US layout.
+fn key_code_of_char(c: char) -> KeyCode {
+    if c == ' ' {
+        KeyCode::Space
+    } else if c.is_ascii_alphabetic() {
+        LETTERS[(c.to_ascii_lowercase() as u8 - b'a') as usize]
+    } else {
+        CHARACTERS
+            .iter()
+            .find(|(_, plain, shifted)| *plain == c || *shifted == c)
+            .map(|(key, _, _)| *key)
+            .unwrap_or(KeyCode::Unidentified(NativeKeyCode::Unidentified))
+    }
+}
@@ -1,15 +1,20 @@
 //! UI
 use bevy::{
     a11y::AccessibilityNode,
+    ecs::spawn::SpawnIter,
     // input::mouse::{MouseScrollUnit, MouseWheel},
     prelude::*,
 };
 
+use crate::event::Completion;
 use accesskit::{Node as Accessible, Role};
+use bevy_asky::AskySet;
 // use bevy_a11y::AccessibilityNode;
 
 const PADDING: Val = Val::Px(3.);
 const LEFT_PADDING: Val = Val::Px(6.);
+const HIGHLIGHT: Color = Color::srgb(0.95, 0.75, 0.25);
+const SELECTED: Color = Color::srgb(0.25, 0.25, 0.30);
 
 /// Root minibuffer node
 #[derive(Component)]
@@ -40,22 +45,90 @@ pub(crate) struct ActContainer;
 /// Autocomplete scrolling state
 #[derive(Component, Default)]
 pub(crate) struct ScrollingList {
-    // position: f32,
-    // selection: Option<usize>,
-    // last_selection: Option<usize>,
+    /// Labels of the completions shown
+    pub labels: Vec<String>,
+    /// Index of the selected completion
+    pub selection: Option<usize>,
+}
+
+impl ScrollingList {
+    /// Show new labels with nothing selected.
+    pub fn set_labels(&mut self, labels: Vec<String>) {
+        self.labels = labels;
+        self.selection = None;
+    }
+
+    /// Select the next label, wrapping around.
+    pub fn select_next(&mut self) {
+        if self.labels.is_empty() {
+            return;
+        }
+        self.selection = Some(match self.selection {
+            Some(i) => (i + 1) % self.labels.len(),
+            None => 0,
+        });
+    }
+
+    /// Select the previous label, wrapping around.
+    pub fn select_previous(&mut self) {
+        if self.labels.is_empty() {
+            return;
+        }
+        self.selection = Some(match self.selection {
+            Some(0) | None => self.labels.len() - 1,
+            Some(i) => i - 1,
+        });
+    }
+
+    /// Return the selected label.
+    pub fn selected(&self) -> Option<&str> {
+        self.selection
+            .and_then(|i| self.labels.get(i))
+            .map(|x| x.as_str())
+    }
 }
 
 /// Autocomplete item
-pub(crate) fn completion_item(label: String) -> (Text, Label, AccessibilityNode) {
+///
+/// Highlighted characters are split into their own colored spans.
+pub(crate) fn completion_item(completion: Completion) -> impl Bundle {
+    let Completion { label, highlights } = completion;
+    let mut spans: Vec<(TextSpan, TextColor)> = Vec::new();
+    let mut run = String::new();
+    let mut run_highlighted = false;
+    for (i, c) in label.chars().enumerate() {
+        let highlighted = highlights.contains(&i);
+        if highlighted != run_highlighted && !run.is_empty() {
+            spans.push(span(std::mem::take(&mut run), run_highlighted));
+        }
+        run_highlighted = highlighted;
+        run.push(c);
+    }
+    if !run.is_empty() {
+        spans.push(span(run, run_highlighted));
+    }
     (
-        Text::new(label),
+        Text::default(),
         Label,
         AccessibilityNode(Accessible::new(Role::ListItem)),
+        Children::spawn(SpawnIter(spans.into_iter())),
+    )
+}
+
+fn span(text: String, highlighted: bool) -> (TextSpan, TextColor) {
+    (
+        TextSpan::new(text),
+        if highlighted {
+            TextColor(HIGHLIGHT)
+        } else {
+            TextColor::default()
+        },
     )
 }
 
 pub(crate) fn plugin(app: &mut App) {
-    app.add_systems(PreStartup, spawn_layout);
+    app.add_systems(PreStartup, spawn_layout)
+        .add_systems(Update, show_selection.after(AskySet::Controller));
 }
 
 /// Create the UI layout.
@@ -91,9 +164,9 @@ fn spawn_layout(mut commands: Commands) {
                             Node {
                                 flex_direction: FlexDirection::Column,
                                 align_self: AlignSelf::FlexEnd,
-                                // height: Val::Percent(50.),
+                                max_height: Val::Vh(50.),
                                 min_width: Val::Percent(25.),
-                                overflow: Overflow::clip_y(),
+                                overflow: Overflow::scroll_y(),
                                 ..default()
                             },
                             BackgroundColor(Color::srgb(0.10, 0.10, 0.10)),
@@ -176,6 +249,48 @@ fn spawn_layout(mut commands: Commands) {
         });
 }
 
+/// Highlight the selected completion and scroll it into view.
+#[allow(clippy::type_complexity)]
+fn show_selection(
+    lists: Query<
+        (&ScrollingList, &Children, &ChildOf),
+        Or<(Changed<ScrollingList>, Changed<Children>)>,
+    >,
+    mut items: Query<(&mut BackgroundColor, &ComputedNode)>,
+    mut containers: Query<(&mut ScrollPosition, &ComputedNode), Without<ScrollingList>>,
+) {
+    for (list, children, child_of) in &lists {
+        let mut top = 0.0;
+        let mut selected_span = None;
+        for (i, child) in children.iter().enumerate() {
+            let Ok((mut color, node)) = items.get_mut(child) else {
+                continue;
+            };
+            let height = node.size().y * node.inverse_scale_factor();
+            if list.selection == Some(i) {
+                *color = BackgroundColor(SELECTED);
+                selected_span = Some((top, top + height));
+            } else {
+                *color = BackgroundColor(Color::NONE);
+            }
+            top += height;
+        }
+        let Ok((mut scroll, container)) = containers.get_mut(child_of.parent()) else {
+            continue;
+        };
+        let Some((top, bottom)) = selected_span else {
+            scroll.offset_y = 0.0;
+            continue;
+        };
+        let view_height = container.size().y * container.inverse_scale_factor();
+        if top < scroll.offset_y {
+            scroll.offset_y = top;
+        } else if bottom > scroll.offset_y + view_height {
+            scroll.offset_y = bottom - view_height;
+        }
+    }
+}
+
 // Scroll the auto complete panel with mouse.
 // pub(crate) fn mouse_scroll(
 //     mut mouse_wheel_events: EventReader<MouseWheel>,
@@ -201,3 +316,24 @@ fn spawn_layout(mut commands: Commands) {
 //         }
 //     }
 // }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn select_wraps() {
+        let mut list = ScrollingList::default();
+        list.select_next();
+        assert_eq!(list.selected(), None);
+        list.set_labels(vec!["a".into(), "b".into(), "c".into()]);
+        list.select_previous();
+        assert_eq!(list.selected(), Some("c"));
+        list.select_next();
+        assert_eq!(list.selected(), Some("a"));
+        list.select_next();
+        assert_eq!(list.selected(), Some("b"));
+        list.set_labels(vec!["d".into()]);
+        assert_eq!(list.selected(), None);
+    }
+}
@@ -0,0 +1,166 @@
+//! Show what may follow a pending key sequence prefix
+//!
+//! Like Emacs' which-key, after the first chords of a key sequence like
+//! `Ctrl-H` have been pending for [Config::which_key], the minibuffer lists
+//! every chord sequence that may follow and the act it runs.
+use crate::{
+    acts::{cache::HotkeyActCache, keymaps::Keymaps, Act, ActFlags},
+    input::{Hotkey, KeyChord},
+    prompt::Prompt,
+    ui::PromptContainer,
+    Config, Minibuffer,
+};
+use bevy::prelude::*;
+use bevy_input_sequence::{cache::KeySequenceCache, input_sequence::KeySequence};
+use std::{borrow::Cow, time::Duration};
+use tabular::{Row, Table};
+
+/// The prefix being typed
+#[derive(Default)]
+pub(crate) struct PendingPrefix {
+    chords: Vec<KeyChord>,
+    since: Duration,
+    /// The help shown and whether the minibuffer was visible before
+    shown: Option<(String, bool)>,
+}
+
+/// Return every live hotkey that continues `prefix` with the rest of its
+/// chords and its act's name, sorted by the rest of the chords.
+pub fn continuations<'a>(
+    prefix: &[KeyChord],
+    acts: impl Iterator<Item = (Entity, &'a Act)> + Clone,
+    keymaps: &Keymaps,
+    cache: &mut HotkeyActCache,
+) -> Vec<(Hotkey, Cow<'static, str>)> {
+    let names: Vec<_> = acts
+        .clone()
+        .filter(|(_, act)| act.flags.contains(ActFlags::Active))
+        .map(|(id, act)| (id, act.name.clone()))
+        .collect();
+    let mut result: Vec<(Hotkey, Cow<'static, str>)> = cache
+        .trie(acts, keymaps)
+        .predictive_search::<Vec<KeyChord>, _>(prefix)
+        .filter(|(chords, _)| chords.len() > prefix.len())
+        .filter_map(|(chords, act_ref)| {
+            names
+                .iter()
+                .find(|(id, _)| *id == act_ref.id)
+                .map(|(_, name)| (Hotkey::new(chords[prefix.len()..].to_vec()), name.clone()))
+        })
+        .collect();
+    result.sort_by_key(|(hotkey, _)| hotkey.to_string());
+    result
+}
+
+/// Format the continuations of `prefix` as a table.
+fn help_message(prefix: &[KeyChord], continuations: &[(Hotkey, Cow<'static, str>)]) -> String {
+    let mut table = Table::new("{:<}  {:<}");
+    for (hotkey, name) in continuations {
+        table.add_row(
+            Row::new()
+                .with_cell(hotkey.to_string())
+                .with_cell(name.as_ref()),
+        );
+    }
+    format!("{}-\n{}", Hotkey::new(prefix.to_vec()), table)
+}
+
+/// Show the continuations of a prefix that has been pending for
+/// [Config::which_key].
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn which_key(
+    config: Res<Config>,
+    time: Res<Time>,
+    sequences: Query<&KeySequence>,
+    sequence_cache: Option<ResMut<KeySequenceCache>>,
+    acts: Query<(Entity, &Act)>,
+    keymaps: Res<Keymaps>,
+    mut hotkey_cache: ResMut<HotkeyActCache>,
+    containers: Query<&Children, With<PromptContainer>>,
+    messages: Query<&Text, Without<Prompt>>,
+    mut pending: Local<PendingPrefix>,
+    mut minibuffer: Minibuffer,
+) {
+    let (Some(delay), Some(mut sequence_cache)) = (config.which_key, sequence_cache) else {
+        return;
+    };
+    let prefix: Vec<KeyChord> = sequence_cache.recall(sequences.iter()).prefix();
+    if prefix != pending.chords {
+        pending.chords = prefix;
+        pending.since = time.elapsed();
+        if pending.chords.is_empty() {
+            if let Some((help, was_visible)) = pending.shown.take() {
+                // Leave the message alone if something replaced the help.
+                let message = containers
+                    .iter()
+                    .flat_map(|children| children.iter())
+                    .find_map(|id| messages.get(id).ok());
+                if message.is_some_and(|text| text.0 == help) {
+                    minibuffer.clear();
+                    if !was_visible {
+                        minibuffer.set_visible(false);
+                    }
+                }
+            }
+            return;
+        }
+        if pending.shown.is_none() {
+            return;
+        }
+    } else if pending.chords.is_empty()
+        || pending.shown.is_some()
+        || time.elapsed() - pending.since < delay
+    {
+        return;
+    }
+    let continuations = continuations(&pending.chords, acts.iter(), &keymaps, &mut hotkey_cache);
+    if continuations.is_empty() {
+        return;
+    }
+    let help = help_message(&pending.chords, &continuations);
+    let was_visible = pending
+        .shown
+        .take()
+        .map(|(_, was_visible)| was_visible)
+        .unwrap_or_else(|| minibuffer.visible());
+    // Never page it; the key sequence is still being typed.
+    minibuffer.show_text(help.clone());
+    minibuffer.set_visible(true);
+    pending.shown = Some((help, was_visible));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{acts::ActBuilder, prelude::keyseq};
+
+    fn describe_key() {}
+    fn describe_act() {}
+    fn list_acts() {}
+
+    #[test]
+    fn help() {
+        let mut world = World::new();
+        let mut builder = ActBuilder::new(describe_key);
+        builder.bind(keyseq! { Ctrl-H K });
+        Command::apply(builder, &mut world);
+        let mut builder = ActBuilder::new(describe_act);
+        builder.bind(keyseq! { Ctrl-H D });
+        Command::apply(builder, &mut world);
+        let mut builder = ActBuilder::new(list_acts);
+        builder.bind(keyseq! { Ctrl-H A B });
+        Command::apply(builder, &mut world);
+
+        let mut cache = HotkeyActCache::default();
+        let keymaps = Keymaps::default();
+        let mut query = world.query::<(Entity, &Act)>();
+        let prefix: Hotkey = "Ctrl-H".parse().unwrap();
+        let items = continuations(&prefix.chords, query.iter(&world), &keymaps, &mut cache);
+        assert_eq!(
+            help_message(&prefix.chords, &items),
+            "Ctrl-H-\nA B  list_acts\nD    describe_act\nK    describe_key\n"
+        );
+        let prefix: Hotkey = "Ctrl-X".parse().unwrap();
+        assert!(continuations(&prefix.chords, query.iter(&world), &keymaps, &mut cache).is_empty());
+    }
+}
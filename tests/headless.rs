@@ -0,0 +1,500 @@
+use bevy::prelude::*;
+use bevy_minibuffer::{event::RunActByNameEvent, prelude::*, prompt::MinibufferState, testing::*};
+
+fn hello(mut minibuffer: Minibuffer) {
+    minibuffer.message("Hello, World!");
+}
+
+fn app() -> App {
+    let mut app = headless_app();
+    app.add_acts((
+        BasicActs::default(),
+        Act::new(hello).bind(keyseq! { Ctrl-H }),
+    ));
+    app.advance(2);
+    app
+}
+
+#[test]
+fn hotkey() {
+    let mut app = app();
+    assert_eq!(app.message(), None);
+    app.press("Ctrl-H");
+    assert_eq!(app.message().as_deref(), Some("Hello, World!"));
+    assert_eq!(app.minibuffer_state(), MinibufferState::Inactive);
+}
+
+#[test]
+fn run_act_by_name() {
+    let mut app = app();
+    app.press("Shift-;");
+    assert_eq!(app.minibuffer_state(), MinibufferState::Active);
+    assert_eq!(app.prompt().as_deref(), Some(":"));
+    app.type_text("hel");
+    assert_eq!(app.input().as_deref(), Some("hel"));
+    app.press("Tab");
+    assert_eq!(app.input().as_deref(), Some("hello"));
+    app.press("Enter");
+    assert_eq!(app.message().as_deref(), Some("Hello, World!"));
+    assert_eq!(app.minibuffer_state(), MinibufferState::Inactive);
+}
+
+#[test]
+fn cancel() {
+    let mut app = app();
+    app.press("Alt-X").type_text("list").press("Escape");
+    assert_eq!(app.minibuffer_state(), MinibufferState::Inactive);
+    assert_eq!(app.prompt(), None);
+    assert_eq!(app.message().as_deref(), Some("canceled"));
+}
+
+#[derive(Resource, Default)]
+struct Count(u32);
+
+fn add(In(n): In<u32>, mut count: ResMut<Count>) {
+    count.0 += n;
+}
+
+#[test]
+fn init_file() {
+    let path = std::env::temp_dir().join(format!("minibuffer_init_{}", std::process::id()));
+    std::fs::write(
+        &path,
+        "# Count up.\nadd 2\n\nadd three\nadd 3\nsubtract 1\n",
+    )
+    .unwrap();
+    let mut app = headless_app();
+    app.init_resource::<Count>()
+        .add_acts(Act::new_with_input(add))
+        .world_mut()
+        .resource_mut::<Config>()
+        .init_file = Some(path.clone());
+    app.advance(2);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(app.world().resource::<Count>().0, 5);
+    let message = app.message().unwrap();
+    let lines: Vec<&str> = message.lines().collect();
+    assert_eq!(lines.len(), 2, "{message}");
+    assert!(
+        lines[0].ends_with(":4: Could not parse \"three\" as u32: invalid digit found in string"),
+        "{message}"
+    );
+    assert!(
+        lines[1].ends_with(":6: No act named 'subtract'."),
+        "{message}"
+    );
+}
+
+fn which_key_app() -> App {
+    let mut app = headless_app();
+    app.add_acts(BasicActs::default())
+        .world_mut()
+        .resource_mut::<Config>()
+        .which_key = Some(Duration::from_millis(100));
+    app.advance(2);
+    app
+}
+
+#[test]
+fn which_key() {
+    let mut app = which_key_app();
+    app.press("Ctrl-H");
+    assert_eq!(app.message(), None);
+    app.advance(6);
+    let help = app.message().unwrap();
+    assert!(help.starts_with("Ctrl-H-\n"), "{help}");
+    assert!(help.contains("K  describe_key"), "{help}");
+    app.press("K");
+    assert_eq!(app.message().as_deref(), Some("Press key: "));
+}
+
+#[test]
+fn which_key_abort() {
+    let mut app = which_key_app();
+    app.press("Ctrl-H").advance(6);
+    assert!(app.message().is_some());
+    app.press("Ctrl-Z");
+    assert_eq!(app.message(), None);
+}
+
+fn increment(mut count: ResMut<Count>) {
+    count.0 += 1;
+}
+
+#[test]
+fn aliases() {
+    let mut app = headless_app();
+    app.init_resource::<Count>().add_acts((
+        BasicActs::default(),
+        Act::new(increment).alias("inc").deprecated_alias("bump"),
+    ));
+    app.advance(2);
+    app.press("Shift-;").type_text("inc").press("Enter");
+    assert_eq!(app.world().resource::<Count>().0, 1);
+    assert_eq!(app.message(), None);
+
+    app.press("Shift-;").type_text("bump").press("Enter");
+    assert_eq!(app.world().resource::<Count>().0, 2);
+    assert_eq!(
+        app.message().as_deref(),
+        Some("'bump' is deprecated; use 'increment'.")
+    );
+
+    app.world_mut().trigger(RunActByNameEvent::new("bump"));
+    app.advance(1);
+    assert_eq!(app.world().resource::<Count>().0, 3);
+    assert_eq!(
+        app.message().as_deref(),
+        Some("'bump' is deprecated; use 'increment'."),
+        "The hint is only given once."
+    );
+}
+
+fn add_undoably(In(n): In<u32>, mut count: ResMut<Count>, mut minibuffer: Minibuffer) {
+    count.0 += n;
+    minibuffer.push_undo(format!("add {n}"), move |world: &mut World| {
+        world.resource_mut::<Count>().0 -= n;
+    });
+}
+
+#[test]
+fn undo_redo() {
+    let mut app = headless_app();
+    app.init_resource::<Count>().add_acts((
+        UndoActs::default(),
+        Act::new_with_input(add_undoably).named("add"),
+    ));
+    app.advance(2);
+    let count = |app: &App| app.world().resource::<Count>().0;
+    for args in ["add 1", "add 2", "add 3"] {
+        app.world_mut()
+            .trigger(RunActByNameEvent::parse(args).unwrap());
+        app.advance(1);
+    }
+    assert_eq!(count(&app), 6);
+
+    app.world_mut().trigger(RunActByNameEvent::new("undo"));
+    app.advance(1);
+    assert_eq!(count(&app), 3);
+    assert_eq!(app.message().as_deref(), Some("Undo add 3."));
+
+    app.world_mut().insert_resource(UniversalArg(Some(2)));
+    app.world_mut().trigger(RunActByNameEvent::new("undo"));
+    app.advance(1);
+    assert_eq!(count(&app), 0);
+    assert_eq!(app.message().as_deref(), Some("Undo add 2, add 1."));
+
+    app.world_mut().insert_resource(UniversalArg(Some(2)));
+    app.world_mut().trigger(RunActByNameEvent::new("redo"));
+    app.advance(1);
+    assert_eq!(count(&app), 3);
+    assert_eq!(app.message().as_deref(), Some("Redo add 1, add 2."));
+
+    // A redone act can be undone again.
+    app.world_mut().insert_resource(UniversalArg(None));
+    app.world_mut().trigger(RunActByNameEvent::new("undo"));
+    app.advance(1);
+    assert_eq!(count(&app), 1);
+
+    // A new act clears what could be redone.
+    app.world_mut()
+        .trigger(RunActByNameEvent::parse("add 5").unwrap());
+    app.advance(1);
+    app.world_mut().trigger(RunActByNameEvent::new("redo"));
+    app.advance(1);
+    assert_eq!(count(&app), 6);
+    assert_eq!(app.message().as_deref(), Some("Nothing to redo."));
+}
+
+#[test]
+fn undo_tape() {
+    let mut app = headless_app();
+    app.init_resource::<Count>().add_acts((
+        UndoActs::default(),
+        TapeActs::default(),
+        Act::new_with_input(add_undoably).named("add"),
+    ));
+    app.advance(2);
+    let count = |app: &App| app.world().resource::<Count>().0;
+    app.press("Q").press("A");
+    for args in ["add 1", "add 2"] {
+        app.world_mut()
+            .trigger(RunActByNameEvent::parse(args).unwrap());
+        app.advance(1);
+    }
+    app.press("Q");
+    assert_eq!(count(&app), 3);
+
+    app.press("Shift-2").press("A");
+    assert_eq!(count(&app), 6);
+    app.world_mut().trigger(RunActByNameEvent::new("undo"));
+    app.advance(1);
+    assert_eq!(count(&app), 3, "The tape is undone as a unit.");
+    assert_eq!(app.message().as_deref(), Some("Undo tape A."));
+
+    app.world_mut().trigger(RunActByNameEvent::new("redo"));
+    app.advance(1);
+    assert_eq!(count(&app), 6);
+    assert_eq!(app.message().as_deref(), Some("Redo tape A."));
+    app.world_mut().trigger(RunActByNameEvent::new("undo"));
+    app.advance(1);
+    assert_eq!(count(&app), 3, "The redone tape is undone as a unit.");
+}
+
+#[test]
+fn categories() {
+    let mut app = headless_app();
+    app.add_acts((BasicActs::default(), Act::new(hello).category("Greetings")));
+    app.advance(2);
+    let list = app
+        .world_mut()
+        .run_system_cached(bevy_minibuffer::acts::basic::list_acts)
+        .unwrap();
+    assert!(list.contains("BasicActs:\n"), "{list}");
+    assert!(list.contains("Greetings:\n"), "{list}");
+
+    app.press("Shift-;").type_text("BasicActs/hel").press("Tab");
+    assert_eq!(app.input().as_deref(), Some("BasicActs/hel"));
+    app.press("Escape");
+
+    app.press("Shift-;").type_text("Greetings/h").press("Tab");
+    assert_eq!(app.input().as_deref(), Some("Greetings/hello"));
+    app.press("Enter");
+    assert_eq!(app.message().as_deref(), Some("Hello, World!"));
+}
+
+#[derive(Resource)]
+struct Editing;
+
+#[test]
+fn run_if() {
+    let mut app = headless_app();
+    app.add_acts((
+        BasicActs::default(),
+        Act::new(hello)
+            .bind(keyseq! { Ctrl-H })
+            .run_if(resource_exists::<Editing>),
+    ));
+    app.advance(2);
+    app.press("Ctrl-H");
+    assert_eq!(app.message(), None);
+    app.press("Shift-;").type_text("hel").press("Tab");
+    assert_eq!(app.input().as_deref(), Some("hel"));
+    app.press("Escape");
+
+    app.insert_resource(Editing).advance(1);
+    app.press("Ctrl-H");
+    assert_eq!(app.message().as_deref(), Some("Hello, World!"));
+    app.press("Shift-;").type_text("hel").press("Tab");
+    assert_eq!(app.input().as_deref(), Some("hello"));
+    app.press("Escape");
+
+    app.world_mut().remove_resource::<Editing>();
+    app.advance(1);
+    app.press("Ctrl-H");
+    assert_eq!(app.message().as_deref(), Some("canceled"));
+}
+
+fn save_game(mut minibuffer: Minibuffer) {
+    minibuffer.message("Saved game.");
+}
+
+fn save_level(mut minibuffer: Minibuffer) {
+    minibuffer.message("Saved level.");
+}
+
+#[test]
+fn keymaps() {
+    let mut app = headless_app();
+    app.add_acts((
+        BasicActs::default(),
+        Act::new(save_game).bind(keyseq! { Ctrl-S }),
+        Act::new(save_level).bind_in("editor", keyseq! { Ctrl-S }),
+    ));
+    app.advance(2);
+    app.press("Ctrl-S");
+    assert_eq!(app.message().as_deref(), Some("Saved game."));
+
+    app.world_mut().resource_mut::<Keymaps>().push("editor");
+    app.advance(1);
+    app.press("Ctrl-S");
+    assert_eq!(app.message().as_deref(), Some("Saved level."));
+    let bindings = app
+        .world_mut()
+        .run_system_cached(bevy_minibuffer::acts::basic::list_key_bindings)
+        .unwrap();
+    assert!(bindings.contains("Ctrl-S (editor)"), "{bindings}");
+    assert!(!bindings.contains("save_game"), "{bindings}");
+
+    app.world_mut().resource_mut::<Keymaps>().pop();
+    app.advance(1);
+    app.press("Ctrl-S");
+    assert_eq!(app.message().as_deref(), Some("Saved game."));
+}
+
+fn count_to_ten(mut minibuffer: Minibuffer) {
+    let lines: Vec<String> = (1..=10).map(|i| format!("line {i}")).collect();
+    minibuffer.message(lines.join("\n"));
+}
+
+#[test]
+fn pager() {
+    let mut app = headless_app();
+    app.add_acts(Act::new(count_to_ten).bind(keyseq! { Ctrl-L }))
+        .world_mut()
+        .resource_mut::<Config>()
+        .pager_lines = Some(3);
+    app.advance(2);
+    app.press("Ctrl-L");
+    assert_eq!(
+        app.message().as_deref(),
+        Some("line 1\nline 2\nline 3\n-- Lines 1-3 of 10 --")
+    );
+    assert_eq!(app.minibuffer_state(), MinibufferState::Active);
+    app.press("Space");
+    assert!(app.message().unwrap().starts_with("line 4\n"));
+    app.press("PageUp");
+    assert!(app.message().unwrap().starts_with("line 1\n"));
+
+    app.press("/");
+    assert_eq!(app.prompt().as_deref(), Some("/"));
+    app.type_text("9").press("Enter");
+    assert_eq!(
+        app.message().as_deref(),
+        Some("line 8\nline 9\nline 10\n-- Lines 8-10 of 10 --")
+    );
+    app.press("Q");
+    assert_eq!(app.message(), None);
+    assert_eq!(app.minibuffer_state(), MinibufferState::Inactive);
+}
+
+#[test]
+fn resource_acts() {
+    let mut app = headless_app();
+    app.add_acts((BasicActs::default(), ResourceActs::default()));
+    app.advance(2);
+    app.press("Shift-;")
+        .type_text("set_resource_field")
+        .press("Enter");
+    assert_eq!(app.prompt().as_deref(), Some("Set field: "));
+    app.type_text("Config.pager_l").press("Tab");
+    assert_eq!(app.input().as_deref(), Some("Config.pager_lines"));
+    app.press("Enter");
+    assert_eq!(app.prompt().as_deref(), Some("Set Config.pager_lines to: "));
+    app.type_text("Some(40)").press("Enter");
+    assert_eq!(
+        app.message().as_deref(),
+        Some("Set Config.pager_lines to Some(40).")
+    );
+    assert_eq!(app.world().resource::<Config>().pager_lines, Some(40));
+
+    app.press("Shift-;")
+        .type_text("show_resource")
+        .press("Enter");
+    app.type_text("Config.pager_lines").press("Enter");
+    let message = app.message().unwrap();
+    assert!(
+        message.starts_with("Config.pager_lines: Some("),
+        "{message}"
+    );
+    assert!(message.contains("40"), "{message}");
+}
+
+#[derive(Component)]
+struct Enemy;
+
+fn despawn_enemy(enemies: Query<(Entity, &Name), With<Enemy>>, mut minibuffer: Minibuffer) {
+    minibuffer
+        .prompt_map("Despawn: ", EntityLookup::from_query(&enemies))
+        .observe(
+            |mut trigger: Trigger<Completed<Entity>>, mut commands: Commands| {
+                if let Some(Ok(id)) = trigger.event_mut().take_result() {
+                    commands.entity(id).despawn();
+                }
+            },
+        );
+}
+
+#[test]
+fn entity_lookup() {
+    let mut app = headless_app();
+    app.add_acts((BasicActs::default(), Act::new(despawn_enemy)));
+    let hero = app.world_mut().spawn(Name::new("hero")).id();
+    let goblin = app.world_mut().spawn((Name::new("goblin"), Enemy)).id();
+    let orc = app.world_mut().spawn((Name::new("orc"), Enemy)).id();
+    app.advance(2);
+    app.press("Shift-;")
+        .type_text("despawn_enemy")
+        .press("Enter");
+    app.type_text("h").press("Tab");
+    assert_eq!(app.input().as_deref(), Some("h"), "Only enemies complete.");
+    app.press("Backspace").type_text("gob").press("Tab");
+    assert_eq!(app.input().as_deref(), Some("goblin"));
+    app.press("Enter");
+    assert!(app.world().get_entity(goblin).is_err());
+    assert!(app.world().get_entity(orc).is_ok());
+    assert!(app.world().get_entity(hero).is_ok());
+}
+
+#[derive(Resource, Reflect, Debug, Clone, Copy, PartialEq)]
+enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+}
+
+fn set_difficulty(mut minibuffer: Minibuffer) {
+    minibuffer
+        .prompt_enum::<Difficulty>("Difficulty: ")
+        .observe(
+            |mut trigger: Trigger<Completed<Difficulty>>, mut commands: Commands| {
+                if let Some(Ok(difficulty)) = trigger.event_mut().take_result() {
+                    commands.insert_resource(difficulty);
+                }
+            },
+        );
+}
+
+#[test]
+fn prompt_enum() {
+    let mut app = headless_app();
+    app.insert_resource(Difficulty::Normal)
+        .add_acts((BasicActs::default(), Act::new(set_difficulty)));
+    app.advance(2);
+    app.press("Shift-;")
+        .type_text("set_difficulty")
+        .press("Enter");
+    app.type_text("H").press("Tab");
+    assert_eq!(app.input().as_deref(), Some("Hard"));
+    app.press("Enter");
+    assert_eq!(*app.world().resource::<Difficulty>(), Difficulty::Hard);
+}
+
+#[derive(States, Reflect, Debug, Clone, PartialEq, Eq, Hash, Default)]
+enum GameState {
+    #[default]
+    Menu,
+    Playing,
+}
+
+#[test]
+fn state_acts() {
+    let mut app = headless_app();
+    app.init_state::<GameState>()
+        .add_acts((BasicActs::default(), StateActs::<GameState>::default()));
+    app.advance(2);
+    app.press("Shift-;")
+        .type_text("set_game_state")
+        .press("Enter");
+    app.type_text("P").press("Tab").press("Enter");
+    app.advance(2);
+    assert_eq!(app.message().as_deref(), Some("Set GameState to Playing."));
+    assert_eq!(
+        *app.world().resource::<State<GameState>>().get(),
+        GameState::Playing
+    );
+    app.press("Shift-;")
+        .type_text("show_game_state")
+        .press("Enter");
+    assert_eq!(app.message().as_deref(), Some("GameState: Playing"));
+}